@@ -0,0 +1,46 @@
+//! Compares naive, blocked and Strassen's multiplications of 1024 x 1024 matrices.
+//!
+//! Run with `cargo bench --bench matrices`
+// Explicit `return` statements are the code style of this crate
+#![allow(clippy::needless_return)]
+
+use rand::Rng;
+use rust_aads::algebra::Matrix;
+use std::time::Instant;
+
+/// Naive triple loop multiplication
+fn multiply(a: &Matrix<i64>, b: &Matrix<i64>) -> Matrix<i64> {
+    let ((n, k), (_, m)) = (a.shape(), b.shape());
+    let mut result = Matrix::new(n, m);
+    for r in 0..n {
+        for c in 0..m {
+            let val: i64 = (0..k).map(|i| a[r][i] * b[i][c]).sum();
+            result.set(r, c, val);
+        }
+    }
+    return result;
+}
+
+/// Returns `n` x `m` matrix with random values from `[-range, range]`
+fn random_matrix(n: usize, m: usize, range: i64) -> Matrix<i64> {
+    let mut rng = rand::thread_rng();
+    return Matrix::from_fn(n, m, |_, _| rng.gen_range(-range..=range));
+}
+
+fn main() {
+    let n: usize = 1024;
+    let (a, b) = (random_matrix(n, n, 1000), random_matrix(n, n, 1000));
+
+    let start = Instant::now();
+    let naive: Matrix<i64> = multiply(&a, &b);
+    println!("naive: {:?}", start.elapsed());
+    let start = Instant::now();
+    let strassen: Matrix<i64> = a.strassen_mul(&b).unwrap();
+    println!("strassen: {:?}", start.elapsed());
+    let start = Instant::now();
+    let tiled: Matrix<i64> = (a * b).unwrap();
+    println!("tiled: {:?}", start.elapsed());
+
+    assert_eq!(naive, tiled);
+    assert_eq!(naive, strassen);
+}
@@ -0,0 +1,55 @@
+//! Compares `SegmentTree` and `BottomUpSegmentTree` on the same queries.
+//!
+//! Run with `cargo bench --bench segment_trees`
+use rand::Rng;
+use rust_aads::monoid::Sum;
+use rust_aads::segtree::{BottomUpSegmentTree, SegmentTree};
+use std::time::Instant;
+
+fn main() {
+    let mut rng = rand::thread_rng();
+    let n: usize = 10_000_000;
+    let queries: usize = 1_000_000;
+    let v: Vec<i64> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
+    let ops: Vec<(usize, usize, i64)> = (0..queries)
+        .map(|_| {
+            let l_idx = rng.gen_range(0..n);
+            (
+                l_idx,
+                rng.gen_range(l_idx + 1..=n),
+                rng.gen_range(-1000..1000),
+            )
+        })
+        .collect();
+
+    let start = Instant::now();
+    let mut recursive: SegmentTree<i64, Sum> = SegmentTree::new(&v, Sum);
+    let built = start.elapsed();
+    let mut recursive_checksum: i64 = 0;
+    for &(l_idx, r_idx, new_val) in ops.iter() {
+        recursive.set(l_idx, new_val);
+        recursive_checksum = recursive_checksum.wrapping_add(recursive.get(l_idx, r_idx));
+    }
+    println!(
+        "SegmentTree: build {:?}, queries {:?}",
+        built,
+        start.elapsed() - built
+    );
+    drop(recursive);
+
+    let start = Instant::now();
+    let mut bottom_up: BottomUpSegmentTree<i64, Sum> = BottomUpSegmentTree::new(&v, Sum);
+    let built = start.elapsed();
+    let mut bottom_up_checksum: i64 = 0;
+    for &(l_idx, r_idx, new_val) in ops.iter() {
+        bottom_up.set(l_idx, new_val);
+        bottom_up_checksum = bottom_up_checksum.wrapping_add(bottom_up.get(l_idx, r_idx));
+    }
+    println!(
+        "BottomUpSegmentTree: build {:?}, queries {:?}",
+        built,
+        start.elapsed() - built
+    );
+
+    assert_eq!(recursive_checksum, bottom_up_checksum);
+}
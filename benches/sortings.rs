@@ -0,0 +1,34 @@
+//! Compares sequential and parallel sorts on 10^7 random numbers.
+//!
+//! Run with `cargo bench --bench sortings`
+use rand::Rng;
+use rust_aads::sortings::{
+    intro_sort, merge_sort, parallel_merge_sort, parallel_sample_sort, ParallelConfig,
+};
+use std::time::Instant;
+
+fn main() {
+    let mut rng = rand::thread_rng();
+    let v: Vec<u64> = (0..10_000_000).map(|_| rng.gen()).collect();
+    let config = ParallelConfig::default();
+    type SortFn = fn(&mut [u64]);
+    let sorts: [(&str, SortFn); 4] = [
+        ("merge_sort", merge_sort),
+        ("intro_sort", intro_sort),
+        ("parallel_merge_sort", |v| {
+            parallel_merge_sort(v, ParallelConfig::default())
+        }),
+        ("parallel_sample_sort", |v| {
+            parallel_sample_sort(v, ParallelConfig::default())
+        }),
+    ];
+
+    println!("{} threads", config.threads);
+    for (name, sort) in sorts {
+        let mut u: Vec<u64> = v.clone();
+        let start = Instant::now();
+        sort(&mut u);
+        println!("{}: {:?}", name, start.elapsed());
+        assert!(u.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
@@ -1,6 +1,6 @@
-use std::ops::{Mul, Neg, Sub};
-use std::ops::{Add, Index, IndexMut};
-use std::process::Output;
+use std::fmt;
+use std::ops::{Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Index, IndexMut, Range};
 
 /// Raises `element` to `power` using recursive
 /// [binary exponentiation](https://en.wikipedia.org/wiki/Exponentiation_by_squaring). Complexity
@@ -9,6 +9,8 @@ use std::process::Output;
 /// # Arguments
 /// * `element` - base of exponentiation
 /// * `power` - power, must be at least 1
+// `u64::is_multiple_of` would raise the minimum supported Rust version to 1.87
+#[allow(clippy::manual_is_multiple_of)]
 pub fn binary_exponentiation<T: Copy + Mul<Output=T>>(element: T, power: u64) -> T {
     // `power` must be at least 1
     assert!(power > 0);
@@ -64,16 +66,48 @@ pub fn extended_euclidean_gcd(a: i32, b: i32, x: &mut i32, y: &mut i32) -> i32 {
 }
 
 
+/// Multiplicative identity, used as diagonal of identity matrix
+pub trait One {
+    /// Returns `1`
+    fn one() -> Self;
+}
+
+/// Implements `One` for integer and floating point types
+macro_rules! impl_one {
+    (integers: $($i:ty),*; floats: $($f:ty),*) => {
+        $(
+            impl One for $i {
+                fn one() -> $i {
+                    return 1;
+                }
+            }
+        )*
+        $(
+            impl One for $f {
+                fn one() -> $f {
+                    return 1.0;
+                }
+            }
+        )*
+    };
+}
+
+impl_one!(
+    integers: u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+    floats: f32, f64
+);
+
+
 /// [Matrix](https://en.wikipedia.org/wiki/Matrix_(mathematics)) structure with generic type
 /// elements
 ///
 /// # Fields:
-/// * `vals` - values, 2-dimensional vector
+/// * `vals` - values in row-major order, element at row `r` and column `c` is `vals[r * cols + c]`
 /// * `rows` - number of rows, `usize`,
 /// * `cols` - number of columns, `usize`
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Matrix<T> where T: Clone {
-    vals: Vec<Vec<T>>,
+    vals: Vec<T>,
     rows: usize,
     cols: usize,
 }
@@ -85,180 +119,1024 @@ impl<T: Clone> Matrix<T>
 {
     pub fn new(r: usize, c: usize) -> Matrix<T> {
         return Matrix {
-            vals: vec![vec![T::default(); c]; r],
+            vals: vec![T::default(); r * c],
             rows: r,
             cols: c,
         };
     }
 }
 
-/// Returns row vector by index
+/// Returns row slice by index
 impl<T: Clone> Index<usize> for Matrix<T> {
-    type Output = Vec<T>;
+    type Output = [T];
 
     fn index(&self, index: usize) -> &Self::Output {
-        return &self.vals[index];
+        assert!(index < self.rows, "row is out of bounds");
+        return &self.vals[index * self.cols..(index + 1) * self.cols];
     }
 }
 
-/// Returns mutable row vector by index
+/// Returns mutable row slice by index
 impl<T: Clone> IndexMut<usize> for Matrix<T> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        return &mut self.vals[index];
+        assert!(index < self.rows, "row is out of bounds");
+        return &mut self.vals[index * self.cols..(index + 1) * self.cols];
     }
 }
 
 /// Get element, Set element and get Access to element
 impl<T: Clone> Matrix<T> {
     pub fn get(&self, r: usize, c: usize) -> Option<&T> {
-        match self.vals.get(r) {
-            Some(v) => v.get(c),
-            None => None,
+        if r >= self.rows || c >= self.cols {
+            return None;
         }
+        return Some(&self.vals[r * self.cols + c]);
     }
 
     pub fn set(&mut self, r: usize, c: usize, new_val: T) {
-        self.vals[r][c] = new_val;
+        assert!(r < self.rows && c < self.cols, "element is out of bounds");
+        self.vals[r * self.cols + c] = new_val;
     }
 
     pub fn access(&mut self, r: usize, c: usize) -> &mut T {
-        return &mut self.vals[r][c];
+        assert!(r < self.rows && c < self.cols, "element is out of bounds");
+        return &mut self.vals[r * self.cols + c];
+    }
+
+    /// Returns number of rows and number of columns
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.cols);
     }
 }
 
 
-/// Negate every element of matrix
-impl<T: Clone + Copy> Neg for Matrix<T> where T: Neg<Output=T> {
-    type Output = Option<Matrix<T>>;
+/// Constructors from values
+impl<T: Clone> Matrix<T> {
+    /// Constructs matrix from vector of rows. Returns `None` if rows have different lengths
+    ///
+    /// # Arguments:
+    /// * `vals` - rows of matrix
+    pub fn from_vec(vals: Vec<Vec<T>>) -> Option<Matrix<T>> {
+        let cols: usize = vals.first().map_or(0, |row| row.len());
+        if vals.iter().any(|row| row.len() != cols) {
+            return None;
+        }
+        return Some(Matrix { rows: vals.len(), cols, vals: vals.into_iter().flatten().collect() });
+    }
 
-    fn neg(self) -> Self::Output {
-        let mut result = self.clone();
-        for r in 0..self.rows {
-            for c in 0..self.cols {
-                match result.get(r, c) {
-                    Some(x) => result.set(r, c, -*x),
-                    None => return None
-                }
+    /// Constructs `r` x `c` matrix, where element at row `i` and column `j` is `f(i, j)`
+    ///
+    /// # Arguments:
+    /// * `r` - number of rows
+    /// * `c` - number of columns
+    /// * `f` - function of row and column, which returns element
+    pub fn from_fn<F>(r: usize, c: usize, mut f: F) -> Matrix<T> where F: FnMut(usize, usize) -> T {
+        let mut vals: Vec<T> = Vec::with_capacity(r * c);
+        for i in 0..r {
+            for j in 0..c {
+                vals.push(f(i, j));
             }
         }
-        return Some(result);
+        return Matrix { vals, rows: r, cols: c };
     }
 }
 
+/// Creates identity matrix
+impl<T: Clone> Matrix<T> where T: Default + One {
+    /// Constructs `n` x `n` identity matrix (ones on the main diagonal, zeros elsewhere)
+    ///
+    /// # Arguments:
+    /// * `n` - number of rows and columns
+    pub fn identity(n: usize) -> Matrix<T> {
+        return Matrix::from_fn(n, n, |i, j| if i == j { T::one() } else { T::default() });
+    }
+}
 
-/// Add matrix to another matrix
-impl<T: Clone + Copy> Add for Matrix<T> where T: Add<Output=T> {
-    type Output = Option<Matrix<T>>;
+/// Iterators, transposition, submatrices and concatenation
+impl<T: Clone> Matrix<T> {
+    /// Returns iterator over rows
+    pub fn iter_rows(&self) -> impl Iterator<Item=&[T]> + '_ {
+        return (0..self.rows).map(move |r| &self[r]);
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        if (self.rows, self.cols) != (rhs.rows, rhs.cols) {
+    /// Returns iterator over elements of column `c` from top to bottom
+    ///
+    /// # Arguments:
+    /// * `c` - index of column
+    pub fn iter_column(&self, c: usize) -> impl Iterator<Item=&T> + '_ {
+        assert!(c < self.cols, "column is out of bounds");
+        return (0..self.rows).map(move |r| &self.vals[r * self.cols + c]);
+    }
+
+    /// Returns iterator over columns, every column is an iterator over its elements
+    pub fn iter_columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T> + '_> + '_ {
+        return (0..self.cols).map(move |c| self.iter_column(c));
+    }
+
+    /// Returns iterator over all elements in row-major order
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        return self.vals.iter();
+    }
+
+    /// Returns transposed matrix, where rows are columns of this matrix
+    pub fn transpose(&self) -> Matrix<T> {
+        return Matrix::from_fn(self.cols, self.rows, |i, j| self[j][i].clone());
+    }
+
+    /// Returns submatrix of rows `[rows.start, rows.end)` and columns `[cols.start, cols.end)` or
+    /// `None`, if ranges are out of bounds
+    ///
+    /// # Arguments:
+    /// * `rows` - range of rows
+    /// * `cols` - range of columns
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> Option<Matrix<T>> {
+        if rows.start > rows.end || rows.end > self.rows
+            || cols.start > cols.end || cols.end > self.cols {
             return None;
         }
-        let mut result = self.clone();
+        return Some(Matrix::from_fn(rows.len(), cols.len(), |i, j| {
+            self[rows.start + i][cols.start + j].clone()
+        }));
+    }
+
+    /// Returns matrix with columns of this matrix followed by columns of `other` or
+    /// `ShapeMismatch`, if numbers of rows are different
+    ///
+    /// # Arguments:
+    /// * `other` - matrix, placed to the right
+    pub fn concat_horizontal(&self, other: &Matrix<T>) -> Result<Matrix<T>, ShapeMismatch> {
+        if self.rows != other.rows {
+            return Err(ShapeMismatch { expected: (self.rows, other.cols), got: other.shape() });
+        }
+        let mut vals: Vec<T> = Vec::with_capacity(self.vals.len() + other.vals.len());
         for r in 0..self.rows {
-            for c in 0..self.cols {
-                let (lhs_val, rhs_val);
-                match result.get(r, c) {
-                    Some(x) => lhs_val = x,
-                    None => return None
-                }
-                match rhs.get(r, c) {
-                    Some(x) => rhs_val = x,
-                    None => return None
+            vals.extend_from_slice(&self[r]);
+            vals.extend_from_slice(&other[r]);
+        }
+        return Ok(Matrix { vals, rows: self.rows, cols: self.cols + other.cols });
+    }
+
+    /// Returns matrix with rows of this matrix followed by rows of `other` or `ShapeMismatch`, if
+    /// numbers of columns are different
+    ///
+    /// # Arguments:
+    /// * `other` - matrix, placed below
+    pub fn concat_vertical(&self, other: &Matrix<T>) -> Result<Matrix<T>, ShapeMismatch> {
+        if self.cols != other.cols {
+            return Err(ShapeMismatch { expected: (other.rows, self.cols), got: other.shape() });
+        }
+        let vals: Vec<T> = self.vals.iter().chain(other.vals.iter()).cloned().collect();
+        return Ok(Matrix { vals, rows: self.rows + other.rows, cols: self.cols });
+    }
+}
+
+
+/// Prints matrix row by row, elements are right-aligned in columns. Precision and width of
+/// formatter are applied to every element, for example `{:.2}`
+impl<T: Clone> fmt::Display for Matrix<T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells: Vec<Vec<String>> = self.iter_rows()
+            .map(|row| row.iter().map(|x| match f.precision() {
+                Some(p) => format!("{:.*}", p, x),
+                None => format!("{}", x)
+            }).collect())
+            .collect();
+        let widest: usize = cells.iter().flatten().map(|s| s.chars().count()).max().unwrap_or(0);
+        let width: usize = widest.max(f.width().unwrap_or(0));
+
+        for (r, row) in cells.iter().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for (c, cell) in row.iter().enumerate() {
+                if c > 0 {
+                    write!(f, " ")?;
                 }
-                result.set(r, c, *lhs_val + *rhs_val);
+                write!(f, "{:>width$}", cell, width = width)?;
             }
         }
+        return Ok(());
+    }
+}
 
-        return Some(result);
+
+/// Error of operation on two matrices with incompatible shapes, such as addition of matrices of
+/// different sizes
+///
+/// # Fields:
+/// * `expected` - shape of right-hand side matrix, which operation requires (for multiplication
+///   only number of rows is required, number of columns is taken from the actual shape)
+/// * `got` - actual shape of right-hand side matrix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShapeMismatch {
+    pub expected: (usize, usize),
+    pub got: (usize, usize),
+}
+
+impl fmt::Display for ShapeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "matrix shape mismatch: expected {}x{}, got {}x{}",
+                      self.expected.0, self.expected.1, self.got.0, self.got.1);
     }
 }
 
+impl std::error::Error for ShapeMismatch {}
 
-/// Sub one matrix from another (possibly could implement using addition and negation)
-impl<T: Clone + Copy> Sub for Matrix<T> where T: Sub<Output=T> {
-    type Output = Option<Matrix<T>>;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        if (self.rows, self.cols) != (rhs.rows, rhs.cols) {
-            return None;
+/// Element-wise operations
+impl<T: Clone + Copy> Matrix<T> {
+    /// Returns `ShapeMismatch` if `rhs` has different shape
+    fn __check_same_shape(&self, rhs: &Matrix<T>) -> Result<(), ShapeMismatch> {
+        if self.shape() != rhs.shape() {
+            return Err(ShapeMismatch { expected: self.shape(), got: rhs.shape() });
         }
-        let mut result = self.clone();
-        for r in 0..self.rows {
-            for c in 0..self.cols {
-                let (lhs_val, rhs_val);
-                match result.get(r, c) {
-                    Some(x) => lhs_val = x,
-                    None => return None
-                }
-                match rhs.get(r, c) {
-                    Some(x) => rhs_val = x,
-                    None => return None
-                }
-                result.set(r, c, *lhs_val - *rhs_val);
-            }
+        return Ok(());
+    }
+
+    /// Combines elements of matrices of the same shape with `f`
+    fn __zip_map<F: Fn(T, T) -> T>(
+        &self,
+        rhs: &Matrix<T>,
+        f: F,
+    ) -> Result<Matrix<T>, ShapeMismatch> {
+        self.__check_same_shape(rhs)?;
+        let vals: Vec<T> = __zip_with(&self.vals, &rhs.vals, f);
+        return Ok(Matrix { vals, rows: self.rows, cols: self.cols });
+    }
+
+    /// Applies `f` to every element of this matrix and corresponding element of `rhs`, panics if
+    /// shapes are different
+    fn __zip_assign<F: Fn(T, T) -> T>(&mut self, rhs: &Matrix<T>, f: F) {
+        if let Err(e) = self.__check_same_shape(rhs) {
+            panic!("{}", e);
+        }
+        for (x, &y) in self.vals.iter_mut().zip(rhs.vals.iter()) {
+            *x = f(*x, y);
         }
+    }
+}
 
-        return Some(result);
+
+/// Negate every element of matrix
+impl<T: Clone + Copy> Neg for &Matrix<T> where T: Neg<Output=T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Self::Output {
+        let vals: Vec<T> = self.vals.iter().map(|&x| -x).collect();
+        return Matrix { vals, rows: self.rows, cols: self.cols };
+    }
+}
+
+/// Negate every element of matrix
+impl<T: Clone + Copy> Neg for Matrix<T> where T: Neg<Output=T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Self::Output {
+        return -&self;
+    }
+}
+
+
+/// Add matrix to another matrix of the same shape
+impl<T: Clone + Copy> Add for &Matrix<T> where T: Add<Output=T> {
+    type Output = Result<Matrix<T>, ShapeMismatch>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        return self.__zip_map(rhs, |x, y| x + y);
+    }
+}
+
+/// Add matrix to another matrix of the same shape
+impl<T: Clone + Copy> Add for Matrix<T> where T: Add<Output=T> {
+    type Output = Result<Matrix<T>, ShapeMismatch>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        return &self + &rhs;
+    }
+}
+
+/// Add matrix of the same shape in place, panics if shapes are different
+impl<T: Clone + Copy> AddAssign<&Matrix<T>> for Matrix<T> where T: Add<Output=T> {
+    fn add_assign(&mut self, rhs: &Matrix<T>) {
+        self.__zip_assign(rhs, |x, y| x + y);
+    }
+}
+
+/// Add matrix of the same shape in place, panics if shapes are different
+impl<T: Clone + Copy> AddAssign for Matrix<T> where T: Add<Output=T> {
+    fn add_assign(&mut self, rhs: Matrix<T>) {
+        *self += &rhs;
+    }
+}
+
+
+/// Sub one matrix from another of the same shape
+impl<T: Clone + Copy> Sub for &Matrix<T> where T: Sub<Output=T> {
+    type Output = Result<Matrix<T>, ShapeMismatch>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        return self.__zip_map(rhs, |x, y| x - y);
+    }
+}
+
+/// Sub one matrix from another of the same shape
+impl<T: Clone + Copy> Sub for Matrix<T> where T: Sub<Output=T> {
+    type Output = Result<Matrix<T>, ShapeMismatch>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        return &self - &rhs;
+    }
+}
+
+/// Sub matrix of the same shape in place, panics if shapes are different
+impl<T: Clone + Copy> SubAssign<&Matrix<T>> for Matrix<T> where T: Sub<Output=T> {
+    fn sub_assign(&mut self, rhs: &Matrix<T>) {
+        self.__zip_assign(rhs, |x, y| x - y);
+    }
+}
+
+/// Sub matrix of the same shape in place, panics if shapes are different
+impl<T: Clone + Copy> SubAssign for Matrix<T> where T: Sub<Output=T> {
+    fn sub_assign(&mut self, rhs: Matrix<T>) {
+        *self -= &rhs;
     }
 }
 
 
+/// Multiply matrix by scalar
+impl<T: Clone + Copy> Mul<T> for &Matrix<T> where T: Mul<Output=T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let vals: Vec<T> = self.vals.iter().map(|&x| x * rhs).collect();
+        return Matrix { vals, rows: self.rows, cols: self.cols };
+    }
+}
+
 /// Multiply matrix by scalar
 impl<T: Clone + Copy> Mul<T> for Matrix<T> where T: Mul<Output=T> {
     type Output = Matrix<T>;
 
     fn mul(self, rhs: T) -> Self::Output {
-        let mut result = self.clone();
-        for r in 0..self.rows {
-            for c in 0..self.cols {
-                let val;
-                match result.get(r, c) {
-                    Some(x) => val = x,
-                    None => continue
+        return &self * rhs;
+    }
+}
+
+/// Multiply matrix by scalar in place
+impl<T: Clone + Copy> MulAssign<T> for Matrix<T> where T: Mul<Output=T> {
+    fn mul_assign(&mut self, rhs: T) {
+        for x in self.vals.iter_mut() {
+            *x = *x * rhs;
+        }
+    }
+}
+
+
+/// Side of square tile in blocked multiplication, three tiles of `f64` fit into L1 cache
+const TILE_SIZE: usize = 32;
+
+/// Minimal size of matrices, which are multiplied by Strassen's algorithm. Smaller ones are
+/// multiplied by blocked multiplication, which is faster for them
+const STRASSEN_THRESHOLD: usize = 128;
+
+/// Blocked (tiled) multiplication of row-major `n` x `k` matrix `a` by `k` x `m` matrix `b`.
+/// Loops are ordered `i-p-j`, so rows of `b` and result are read sequentially
+///
+/// Time complexity - O(N*K*M)
+fn __tiled_mul<T>(a: &[T], b: &[T], n: usize, k: usize, m: usize) -> Vec<T>
+    where
+        T: Copy + Default + Add<Output=T> + Mul<Output=T>,
+{
+    let mut result: Vec<T> = vec![T::default(); n * m];
+    for i0 in (0..n).step_by(TILE_SIZE) {
+        for p0 in (0..k).step_by(TILE_SIZE) {
+            for j0 in (0..m).step_by(TILE_SIZE) {
+                let j1: usize = (j0 + TILE_SIZE).min(m);
+                for i in i0..(i0 + TILE_SIZE).min(n) {
+                    for p in p0..(p0 + TILE_SIZE).min(k) {
+                        let x: T = a[i * k + p];
+                        let row: &mut [T] = &mut result[i * m + j0..i * m + j1];
+                        let b_row: &[T] = &b[p * m + j0..p * m + j1];
+                        for (r, &y) in row.iter_mut().zip(b_row.iter()) {
+                            *r = *r + x * y;
+                        }
+                    }
                 }
-                result.set(r, c, *val * rhs);
             }
         }
+    }
+    return result;
+}
 
-        return result;
+/// Returns quadrant `(qi, qj)` of row-major `n` x `n` matrix `a`, `n` is even
+fn __quadrant<T: Copy>(a: &[T], n: usize, qi: usize, qj: usize) -> Vec<T> {
+    let h: usize = n / 2;
+    let mut result: Vec<T> = Vec::with_capacity(h * h);
+    for r in qi * h..(qi + 1) * h {
+        result.extend_from_slice(&a[r * n + qj * h..r * n + (qj + 1) * h]);
     }
+    return result;
 }
 
+/// Element-wise combination of two buffers of equal length
+fn __zip_with<T: Copy, F: Fn(T, T) -> T>(a: &[T], b: &[T], f: F) -> Vec<T> {
+    return a.iter().zip(b.iter()).map(|(&x, &y)| f(x, y)).collect();
+}
 
-/// Multiply matrix by column
+/// [Strassen's multiplication](https://en.wikipedia.org/wiki/Strassen_algorithm) of row-major
+/// `n` x `n` matrices. Matrices of odd size are padded with zero row and column
+///
+/// Time complexity - O(N^log2(7)), about O(N^2.81)
+fn __strassen<T>(a: &[T], b: &[T], n: usize) -> Vec<T>
+    where
+        T: Copy + Default + Add<Output=T> + Sub<Output=T> + Mul<Output=T>,
+{
+    if n < STRASSEN_THRESHOLD {
+        return __tiled_mul(a, b, n, n, n);
+    }
+    if n % 2 == 1 {
+        let pad = |x: &[T]| -> Vec<T> {
+            let mut padded: Vec<T> = vec![T::default(); (n + 1) * (n + 1)];
+            for r in 0..n {
+                padded[r * (n + 1)..r * (n + 1) + n].copy_from_slice(&x[r * n..(r + 1) * n]);
+            }
+            return padded;
+        };
+        let product: Vec<T> = __strassen(&pad(a), &pad(b), n + 1);
+        return (0..n).flat_map(|r| product[r * (n + 1)..r * (n + 1) + n].to_vec()).collect();
+    }
+
+    let h: usize = n / 2;
+    let (add, sub) = (|x: T, y: T| x + y, |x: T, y: T| x - y);
+    let (a11, a12) = (__quadrant(a, n, 0, 0), __quadrant(a, n, 0, 1));
+    let (a21, a22) = (__quadrant(a, n, 1, 0), __quadrant(a, n, 1, 1));
+    let (b11, b12) = (__quadrant(b, n, 0, 0), __quadrant(b, n, 0, 1));
+    let (b21, b22) = (__quadrant(b, n, 1, 0), __quadrant(b, n, 1, 1));
+
+    let m1: Vec<T> = __strassen(&__zip_with(&a11, &a22, add), &__zip_with(&b11, &b22, add), h);
+    let m2: Vec<T> = __strassen(&__zip_with(&a21, &a22, add), &b11, h);
+    let m3: Vec<T> = __strassen(&a11, &__zip_with(&b12, &b22, sub), h);
+    let m4: Vec<T> = __strassen(&a22, &__zip_with(&b21, &b11, sub), h);
+    let m5: Vec<T> = __strassen(&__zip_with(&a11, &a12, add), &b22, h);
+    let m6: Vec<T> = __strassen(&__zip_with(&a21, &a11, sub), &__zip_with(&b11, &b12, add), h);
+    let m7: Vec<T> = __strassen(&__zip_with(&a12, &a22, sub), &__zip_with(&b21, &b22, add), h);
+
+    // C11 = M1 + M4 - M5 + M7, C12 = M3 + M5, C21 = M2 + M4, C22 = M1 - M2 + M3 + M6
+    let c11: Vec<T> = __zip_with(&__zip_with(&m1, &m4, add), &__zip_with(&m7, &m5, sub), add);
+    let c12: Vec<T> = __zip_with(&m3, &m5, add);
+    let c21: Vec<T> = __zip_with(&m2, &m4, add);
+    let c22: Vec<T> = __zip_with(&__zip_with(&m1, &m2, sub), &__zip_with(&m3, &m6, add), add);
+
+    let mut result: Vec<T> = Vec::with_capacity(n * n);
+    for r in 0..h {
+        result.extend_from_slice(&c11[r * h..(r + 1) * h]);
+        result.extend_from_slice(&c12[r * h..(r + 1) * h]);
+    }
+    for r in 0..h {
+        result.extend_from_slice(&c21[r * h..(r + 1) * h]);
+        result.extend_from_slice(&c22[r * h..(r + 1) * h]);
+    }
+    return result;
+}
+
+
+/// Multiply matrix by matrix using blocked multiplication. Returns `ShapeMismatch` if number of
+/// columns of `self` is not equal to number of rows of `rhs`
+///
+/// `*` never switches to Strassen's algorithm, even for large square matrices: it is implemented
+/// for any `T` with `Add` and `Mul` (unsigned integers, custom types), for which `Sub` may not
+/// exist or intermediate differences may overflow, and the element type can't be inspected
+/// without specialization. Strassen's rounding errors for floats are also larger. Use
+/// `strassen_mul` explicitly for large signed or floating point matrices.
+impl<T: Clone + Copy + Default> Mul for &Matrix<T> where T: Mul<Output=T> + Add<Output=T> {
+    type Output = Result<Matrix<T>, ShapeMismatch>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.cols != rhs.rows {
+            return Err(ShapeMismatch { expected: (self.cols, rhs.cols), got: rhs.shape() });
+        }
+
+        let vals: Vec<T> = __tiled_mul(&self.vals, &rhs.vals, self.rows, self.cols, rhs.cols);
+        return Ok(Matrix { vals, rows: self.rows, cols: rhs.cols });
+    }
+}
+
+/// Multiply matrix by matrix using blocked multiplication. Returns `ShapeMismatch` if number of
+/// columns of `self` is not equal to number of rows of `rhs`
 impl<T: Clone + Copy + Default> Mul for Matrix<T> where T: Mul<Output=T> + Add<Output=T> {
-    type Output = Option<Matrix<T>>;
+    type Output = Result<Matrix<T>, ShapeMismatch>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        if !(self.cols == rhs.rows) {
+        return &self * &rhs;
+    }
+}
+
+/// Multiply matrix by matrix in place, panics if number of columns of `self` is not equal to
+/// number of rows of `rhs`
+impl<T: Clone + Copy + Default> MulAssign<&Matrix<T>> for Matrix<T>
+    where
+        T: Mul<Output=T> + Add<Output=T>,
+{
+    fn mul_assign(&mut self, rhs: &Matrix<T>) {
+        *self = match &*self * rhs {
+            Ok(product) => product,
+            Err(e) => panic!("{}", e)
+        };
+    }
+}
+
+/// Multiply matrix by matrix in place, panics if number of columns of `self` is not equal to
+/// number of rows of `rhs`
+impl<T: Clone + Copy + Default> MulAssign for Matrix<T> where T: Mul<Output=T> + Add<Output=T> {
+    fn mul_assign(&mut self, rhs: Matrix<T>) {
+        *self *= &rhs;
+    }
+}
+
+
+/// Strassen's multiplication
+impl<T: Clone + Copy + Default> Matrix<T> where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> {
+    /// Multiplies square matrices with
+    /// [Strassen's algorithm](https://en.wikipedia.org/wiki/Strassen_algorithm) in
+    /// O(N^2.81), blocks smaller than `STRASSEN_THRESHOLD` are multiplied directly. Returns
+    /// `ShapeMismatch` if matrices are not square or have different sizes. It is never called by
+    /// `*`, which uses blocked multiplication for every size.
+    ///
+    /// **NOTE:** intermediate values are sums and differences of elements, so unsigned types can
+    /// overflow even if the product fits. For floats rounding errors are a bit larger than with
+    /// `*`.
+    ///
+    /// # Arguments:
+    /// * `rhs` - right-hand side matrix
+    pub fn strassen_mul(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeMismatch> {
+        if self.rows != self.cols {
+            return Err(ShapeMismatch { expected: (self.rows, self.rows), got: self.shape() });
+        }
+        self.__check_same_shape(rhs)?;
+
+        let vals: Vec<T> = __strassen(&self.vals, &rhs.vals, self.rows);
+        return Ok(Matrix { vals, rows: self.rows, cols: self.cols });
+    }
+}
+
+
+/// Matrix exponentiation
+impl<T: Clone + Copy + Default> Matrix<T> where T: One + Add<Output=T> + Mul<Output=T> {
+    /// Raises square matrix to power `k` by binary exponentiation (square-and-multiply), `k = 0`
+    /// gives identity matrix. Returns `None` if matrix is not square
+    ///
+    /// Time complexity - O(N^3 * logK)
+    ///
+    /// # Arguments:
+    /// * `k` - power
+    pub fn pow(&self, k: u64) -> Option<Matrix<T>> {
+        if self.rows != self.cols {
             return None;
         }
 
-        let mut result = Matrix::new(self.rows, rhs.cols);
-        for r in 0..result.rows {
-            for c in 0..result.cols {
-                for i in 0..self.cols {
-                    let val;
-                    match result.get(r, c) {
-                        Some(x) => val = x,
-                        None => return None
-                    }
-                    let (lhs_val, rhs_val);
-                    match self.get(r, i) {
-                        Some(x) => lhs_val = x,
-                        None => return None
-                    }
-                    match self.get(i, c) {
-                        Some(x) => rhs_val = x,
-                        None => return None
-                    }
+        let mut result: Matrix<T> = Matrix::identity(self.rows);
+        let mut base: Matrix<T> = self.clone();
+        let mut k: u64 = k;
+        while k > 0 {
+            // Shapes of square matrices always match
+            if k & 1 == 1 {
+                result = (&result * &base).unwrap();
+            }
+            k >>= 1;
+            if k > 0 {
+                base = (&base * &base).unwrap();
+            }
+        }
+        return Some(result);
+    }
+}
+
 
-                    result.set(r, c, *val + *lhs_val * *rhs_val);
+/// Modular matrix arithmetic, products are computed in `u128`, so any modulo up to `u64::MAX`
+/// can be used
+impl Matrix<u64> {
+    /// Multiplies matrices modulo `modulo`. Returns `ShapeMismatch` if number of columns of `self`
+    /// is not equal to number of rows of `rhs`
+    ///
+    /// # Arguments:
+    /// * `rhs` - right-hand side matrix
+    /// * `modulo` - modulo, must be positive
+    pub fn mul_mod(&self, rhs: &Matrix<u64>, modulo: u64) -> Result<Matrix<u64>, ShapeMismatch> {
+        assert!(modulo > 0, "modulo must be positive");
+        if self.cols != rhs.rows {
+            return Err(ShapeMismatch { expected: (self.cols, rhs.cols), got: rhs.shape() });
+        }
+
+        let m: u128 = modulo as u128;
+        let mut result: Vec<u128> = vec![0; self.rows * rhs.cols];
+        for i in 0..self.rows {
+            let row: &mut [u128] = &mut result[i * rhs.cols..(i + 1) * rhs.cols];
+            for p in 0..self.cols {
+                let x: u128 = self.vals[i * self.cols + p] as u128 % m;
+                for (r, &y) in row.iter_mut().zip(rhs[p].iter()) {
+                    *r = (*r + x * (y as u128 % m)) % m;
                 }
             }
         }
+        let vals: Vec<u64> = result.into_iter().map(|x| x as u64).collect();
+        return Ok(Matrix { vals, rows: self.rows, cols: rhs.cols });
+    }
 
+    /// Raises square matrix to power `k` modulo `modulo`. Returns `None` if matrix is not square
+    ///
+    /// Time complexity - O(N^3 * logK)
+    ///
+    /// # Arguments:
+    /// * `k` - power
+    /// * `modulo` - modulo, must be positive
+    pub fn pow_mod(&self, k: u64, modulo: u64) -> Option<Matrix<u64>> {
+        assert!(modulo > 0, "modulo must be positive");
+        if self.rows != self.cols {
+            return None;
+        }
+
+        // Identity matrix modulo 1 is zero matrix
+        let mut result: Matrix<u64> =
+            Matrix::from_fn(self.rows, self.rows, |r, c| (r == c) as u64 % modulo);
+        let mut base: Matrix<u64> = self.clone();
+        let mut k: u64 = k;
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result.mul_mod(&base, modulo).unwrap();
+            }
+            k >>= 1;
+            if k > 0 {
+                base = base.mul_mod(&base, modulo).unwrap();
+            }
+        }
         return Some(result);
     }
-}
\ No newline at end of file
+}
+
+
+/// Returns [companion matrix](https://en.wikipedia.org/wiki/Companion_matrix) of linear
+/// recurrence: first row is `coeffs`, ones are below main diagonal. It maps vector of `d`
+/// consecutive terms `(a[i + d - 1], ..., a[i])` to `(a[i + d], ..., a[i + 1])`
+fn __companion<T: Clone + Copy + Default + One>(coeffs: &[T]) -> Matrix<T> {
+    return Matrix::from_fn(coeffs.len(), coeffs.len(), |r, c| match r {
+        0 => coeffs[c],
+        _ if c + 1 == r => T::one(),
+        _ => T::default()
+    });
+}
+
+/// Returns column of initial terms in reverse order `(a[d - 1], ..., a[0])`
+fn __initial_state<T: Clone + Copy>(init: &[T]) -> Matrix<T> {
+    return Matrix::from_fn(init.len(), 1, |r, _| init[init.len() - 1 - r]);
+}
+
+/// Computes `n`-th term of
+/// [linear recurrence](https://en.wikipedia.org/wiki/Linear_recurrence_with_constant_coefficients)
+/// `a[i] = coeffs[0] * a[i - 1] + coeffs[1] * a[i - 2] + ... + coeffs[d - 1] * a[i - d]` with
+/// exponentiation of its companion matrix. For example, Fibonacci numbers have `coeffs = [1, 1]`
+/// and `init = [0, 1]`.
+///
+/// Time complexity - O(D^3 * logN), where D is order of recurrence
+///
+/// # Arguments:
+/// * `coeffs` - coefficients of recurrence, non-empty
+/// * `init` - first `d` terms `a[0], ..., a[d - 1]`
+/// * `n` - index of term
+pub fn linear_recurrence<T>(coeffs: &[T], init: &[T], n: u64) -> T
+    where
+        T: Clone + Copy + Default + One + Add<Output=T> + Mul<Output=T>,
+{
+    assert!(!coeffs.is_empty(), "recurrence must have at least one coefficient");
+    assert_eq!(coeffs.len(), init.len(), "number of initial terms must be equal to order");
+
+    let d: u64 = coeffs.len() as u64;
+    if n < d {
+        return init[n as usize];
+    }
+    let power: Matrix<T> = __companion(coeffs).pow(n - d + 1).unwrap();
+    return (power * __initial_state(init)).unwrap()[0][0];
+}
+
+/// Computes `n`-th term of linear recurrence modulo `modulo` (see `linear_recurrence`), so `n`
+/// can be huge without overflow. Modulo doesn't have to be prime.
+///
+/// Time complexity - O(D^3 * logN), where D is order of recurrence
+///
+/// # Arguments:
+/// * `coeffs` - coefficients of recurrence, non-empty
+/// * `init` - first `d` terms `a[0], ..., a[d - 1]`
+/// * `n` - index of term
+/// * `modulo` - modulo, must be positive
+pub fn linear_recurrence_mod(coeffs: &[u64], init: &[u64], n: u64, modulo: u64) -> u64 {
+    assert!(!coeffs.is_empty(), "recurrence must have at least one coefficient");
+    assert_eq!(coeffs.len(), init.len(), "number of initial terms must be equal to order");
+    assert!(modulo > 0, "modulo must be positive");
+
+    let d: u64 = coeffs.len() as u64;
+    if n < d {
+        return init[n as usize] % modulo;
+    }
+    let power: Matrix<u64> = __companion(coeffs).pow_mod(n - d + 1, modulo).unwrap();
+    return power.mul_mod(&__initial_state(init), modulo).unwrap()[0][0];
+}
+
+
+/// Implements [Gaussian elimination](https://en.wikipedia.org/wiki/Gaussian_elimination) with
+/// partial pivoting for floating point matrices. Values, which absolute value is not greater than
+/// `N * EPSILON * max|a_ij|`, are considered zero.
+///
+/// Time complexity of every operation - O(N^3)
+macro_rules! impl_float_elimination {
+    ($($t:ty),*) => {
+        $(
+            impl Matrix<$t> {
+                /// Returns tolerance, below which pivots are considered zero
+                fn __eps(&self) -> $t {
+                    let max_abs: $t = self.vals.iter().fold(0.0, |m: $t, x| m.max(x.abs()));
+                    return max_abs * <$t>::EPSILON * (self.rows.max(self.cols) as $t);
+                }
+
+                /// Gauss-Jordan elimination of first `cols` columns of `a` (other columns are
+                /// augmented ones). Every pivot becomes `1` and other values in its column become
+                /// `0`. Returns pivot columns and determinant of eliminated part (`0` if any column
+                /// has no pivot)
+                fn __gauss_jordan(a: &mut [Vec<$t>], cols: usize, eps: $t) -> (Vec<usize>, $t) {
+                    let rows: usize = a.len();
+                    let mut pivots: Vec<usize> = Vec::new();
+                    let mut det: $t = 1.0;
+                    for c in 0..cols {
+                        let r: usize = pivots.len();
+                        if r == rows {
+                            det = 0.0;
+                            break;
+                        }
+
+                        // Partial pivoting: row with the largest absolute value in column
+                        let p: usize = (r..rows)
+                            .max_by(|&i, &j| a[i][c].abs().total_cmp(&a[j][c].abs()))
+                            .unwrap();
+                        if a[p][c].abs() <= eps {
+                            det = 0.0;
+                            continue;
+                        }
+                        if p != r {
+                            a.swap(p, r);
+                            det = -det;
+                        }
+
+                        let pivot: $t = a[r][c];
+                        det *= pivot;
+                        for x in a[r].iter_mut() {
+                            *x /= pivot;
+                        }
+                        let pivot_row: Vec<$t> = a[r].clone();
+                        for i in 0..rows {
+                            let factor: $t = a[i][c];
+                            if i == r || factor == 0.0 {
+                                continue;
+                            }
+                            for (x, y) in a[i].iter_mut().zip(pivot_row.iter()) {
+                                *x -= factor * *y;
+                            }
+                        }
+                        pivots.push(c);
+                    }
+                    return (pivots, det);
+                }
+
+                /// Returns determinant of square matrix
+                pub fn determinant(&self) -> $t {
+                    assert_eq!(self.rows, self.cols, "matrix is not square");
+
+                    let mut a: Vec<Vec<$t>> = self.iter_rows().map(|row| row.to_vec()).collect();
+                    return Self::__gauss_jordan(&mut a, self.cols, self.__eps()).1;
+                }
+
+                /// Returns rank of matrix (number of linearly independent rows)
+                pub fn rank(&self) -> usize {
+                    let mut a: Vec<Vec<$t>> = self.iter_rows().map(|row| row.to_vec()).collect();
+                    return Self::__gauss_jordan(&mut a, self.cols, self.__eps()).0.len();
+                }
+
+                /// Returns inverse matrix or `None`, if matrix is not square or is singular
+                pub fn inverse(&self) -> Option<Matrix<$t>> {
+                    if self.rows != self.cols {
+                        return None;
+                    }
+
+                    let n: usize = self.rows;
+                    let mut a: Vec<Vec<$t>> = self.iter_rows().map(|row| row.to_vec()).collect();
+                    for (r, row) in a.iter_mut().enumerate() {
+                        row.extend((0..n).map(|c| if r == c { 1.0 } else { 0.0 }));
+                    }
+                    let (pivots, _) = Self::__gauss_jordan(&mut a, n, self.__eps());
+                    if pivots.len() < n {
+                        return None;
+                    }
+
+                    let right: Vec<Vec<$t>> = a.into_iter()
+                        .map(|mut row| row.split_off(n))
+                        .collect();
+                    return Matrix::from_vec(right);
+                }
+
+                /// Solves system of linear equations `A * x = b`. Returns `None` if there is no
+                /// solution, otherwise returns one of solutions (free variables are `0`)
+                ///
+                /// # Arguments:
+                /// * `b` - right-hand side, one value per row
+                pub fn solve(&self, b: &[$t]) -> Option<Vec<$t>> {
+                    assert_eq!(self.rows, b.len(), "right-hand side doesn't match rows");
+
+                    let mut a: Vec<Vec<$t>> = self.iter_rows().map(|row| row.to_vec()).collect();
+                    for (row, x) in a.iter_mut().zip(b.iter()) {
+                        row.push(*x);
+                    }
+                    let b_max: $t = b.iter().fold(0.0, |m: $t, x| m.max(x.abs()));
+                    let eps: $t = self.__eps().max(b_max * <$t>::EPSILON * (self.rows as $t));
+                    let (pivots, _) = Self::__gauss_jordan(&mut a, self.cols, eps);
+
+                    // Rows without pivots are zero, so they must have zero right-hand side
+                    if a[pivots.len()..].iter().any(|row| row[self.cols].abs() > eps) {
+                        return None;
+                    }
+                    let mut x: Vec<$t> = vec![0.0; self.cols];
+                    for (r, &c) in pivots.iter().enumerate() {
+                        x[c] = a[r][self.cols];
+                    }
+                    return Some(x);
+                }
+            }
+        )*
+    };
+}
+
+impl_float_elimination!(f32, f64);
+
+
+/// Implements fraction-free ([Bareiss](https://en.wikipedia.org/wiki/Bareiss_algorithm))
+/// elimination for integer matrices, so results are exact. Every intermediate value is a minor of
+/// the matrix, computations are made in `i128`. Products of two minors may not fit into `i128`
+/// even for `i64` matrices with large values, then operations panic with "elimination overflows
+/// i128" message.
+///
+/// Time complexity of every operation - O(N^3)
+macro_rules! impl_integer_elimination {
+    ($($t:ty),*) => {
+        $(
+            impl Matrix<$t> {
+                /// Fraction-free elimination of first `cols` columns of `a` (other columns are
+                /// augmented ones). Eliminates rows below pivots or, if `jordan` is `true`, all
+                /// other rows. After Gauss-Jordan elimination of non-singular matrix every pivot
+                /// is equal to determinant of (row-permuted) matrix. Returns pivot columns and
+                /// sign of rows permutation
+                fn __bareiss(a: &mut [Vec<i128>], cols: usize, jordan: bool) -> (Vec<usize>, i128) {
+                    let rows: usize = a.len();
+                    let mut pivots: Vec<usize> = Vec::new();
+                    let mut sign: i128 = 1;
+                    let mut prev: i128 = 1;
+                    for c in 0..cols {
+                        let r: usize = pivots.len();
+                        if r == rows {
+                            break;
+                        }
+
+                        let p: usize = match (r..rows).find(|&i| a[i][c] != 0) {
+                            Some(p) => p,
+                            None => continue
+                        };
+                        if p != r {
+                            a.swap(p, r);
+                            sign = -sign;
+                        }
+
+                        let pivot_row: Vec<i128> = a[r].clone();
+                        let first: usize = if jordan { 0 } else { r + 1 };
+                        for i in first..rows {
+                            if i == r {
+                                continue;
+                            }
+                            // Division is exact, result is a minor of the matrix
+                            let factor: i128 = a[i][c];
+                            for j in 0..pivot_row.len() {
+                                a[i][j] = pivot_row[c]
+                                    .checked_mul(a[i][j])
+                                    .zip(factor.checked_mul(pivot_row[j]))
+                                    .and_then(|(x, y)| x.checked_sub(y))
+                                    .and_then(|x| x.checked_div(prev))
+                                    .expect("elimination overflows i128");
+                            }
+                        }
+                        prev = pivot_row[c];
+                        pivots.push(c);
+                    }
+                    return (pivots, sign);
+                }
+
+                /// Returns copy of values, converted to `i128`
+                fn __widen(&self) -> Vec<Vec<i128>> {
+                    return self.iter_rows()
+                        .map(|row| row.iter().map(|&x| x as i128).collect())
+                        .collect();
+                }
+
+                /// Gauss-Jordan elimination of matrix, augmented with `extra` columns. Returns
+                /// determinant and augmented columns, multiplied by it, or `None` if matrix is
+                /// singular
+                fn __jordan(&self, extra: &[Vec<$t>]) -> Option<(i128, Vec<Vec<i128>>)> {
+                    let n: usize = self.rows;
+                    if n == 0 {
+                        // Determinant of empty matrix is `1`, there are no rows to solve
+                        return Some((1, Vec::new()));
+                    }
+                    let mut a: Vec<Vec<i128>> = self.__widen();
+                    for (row, ext) in a.iter_mut().zip(extra.iter()) {
+                        row.extend(ext.iter().map(|&x| x as i128));
+                    }
+                    let (pivots, _) = Self::__bareiss(&mut a, n, true);
+                    if pivots.len() < n {
+                        return None;
+                    }
+
+                    // Pivots are equal to determinant of row-permuted matrix and augmented part is
+                    // multiplied by it, so sign of permutation cancels out
+                    let det: i128 = a[n - 1][n - 1];
+                    let right: Vec<Vec<i128>> = a.iter_mut().map(|row| row.split_off(n)).collect();
+                    return Some((det, right));
+                }
+
+                /// Returns determinant of square matrix
+                pub fn determinant(&self) -> $t {
+                    assert_eq!(self.rows, self.cols, "matrix is not square");
+
+                    let n: usize = self.rows;
+                    if n == 0 {
+                        return 1;
+                    }
+                    let mut a: Vec<Vec<i128>> = self.__widen();
+                    let (pivots, sign) = Self::__bareiss(&mut a, n, false);
+                    if pivots.len() < n {
+                        return 0;
+                    }
+                    return <$t>::try_from(sign * a[n - 1][n - 1]).expect("determinant overflows");
+                }
+
+                /// Returns rank of matrix (number of linearly independent rows)
+                pub fn rank(&self) -> usize {
+                    let mut a: Vec<Vec<i128>> = self.__widen();
+                    return Self::__bareiss(&mut a, self.cols, false).0.len();
+                }
+
+                /// Returns inverse matrix or `None`, if matrix is not square, is singular or its
+                /// inverse is not an integer matrix (determinant is not `1` or `-1`)
+                pub fn inverse(&self) -> Option<Matrix<$t>> {
+                    if self.rows != self.cols {
+                        return None;
+                    }
+
+                    let n: usize = self.rows;
+                    let identity: Vec<Vec<$t>> = (0..n)
+                        .map(|r| (0..n).map(|c| if r == c { 1 } else { 0 }).collect())
+                        .collect();
+                    let (det, right) = self.__jordan(&identity)?;
+                    if det != 1 && det != -1 {
+                        return None;
+                    }
+
+                    let mut result: Matrix<$t> = Matrix::new(n, n);
+                    for (r, row) in right.iter().enumerate() {
+                        for (c, &x) in row.iter().enumerate() {
+                            result.set(r, c, <$t>::try_from(x * det).ok()?);
+                        }
+                    }
+                    return Some(result);
+                }
+
+                /// Solves system of linear equations `A * x = b` for square matrix. Returns `None`
+                /// if matrix is not square, is singular or solution is not an integer vector
+                ///
+                /// # Arguments:
+                /// * `b` - right-hand side, one value per row
+                pub fn solve(&self, b: &[$t]) -> Option<Vec<$t>> {
+                    assert_eq!(self.rows, b.len(), "right-hand side doesn't match rows");
+                    if self.rows != self.cols {
+                        return None;
+                    }
+
+                    let column: Vec<Vec<$t>> = b.iter().map(|&x| vec![x]).collect();
+                    let (det, right) = self.__jordan(&column)?;
+                    let mut x: Vec<$t> = Vec::with_capacity(self.cols);
+                    for row in right.iter() {
+                        if row[0] % det != 0 {
+                            return None;
+                        }
+                        x.push(<$t>::try_from(row[0] / det).ok()?);
+                    }
+                    return Some(x);
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_elimination!(i8, i16, i32, i64, isize);
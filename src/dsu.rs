@@ -0,0 +1,434 @@
+use crate::monoid::Monoid;
+
+/// [Disjoint Set Union](https://en.wikipedia.org/wiki/Disjoint-set_data_structure) (union-find),
+/// that allows to merge sets of elements `0..n` and to check if two elements are in the same set.
+///
+/// Uses union by size and path compression.
+///
+/// Described [here](https://cp-algorithms.com/data_structures/disjoint_set_union.html).
+///
+/// Memory complexity - O(N)
+///
+/// Time complexity (amortized):
+/// * `find` - O(α(N)), where α is inverse Ackermann function
+/// * `union` - O(α(N))
+pub struct Dsu {
+    /// `parent[i]` is parent of `i`, roots are parents of themselves
+    parent: Vec<usize>,
+    /// `size[i]` is size of component, if `i` is a root
+    size: Vec<usize>,
+    /// `components` is number of components
+    components: usize,
+}
+
+impl Dsu {
+    /// Constructs and returns DSU of `n` single-element components
+    ///
+    /// # Arguments:
+    /// * `n` - number of elements
+    pub fn new(n: usize) -> Dsu {
+        return Dsu {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        };
+    }
+
+    /// Returns number of elements
+    pub fn len(&self) -> usize {
+        return self.parent.len();
+    }
+
+    /// Returns `true` if there are no elements
+    pub fn is_empty(&self) -> bool {
+        return self.parent.is_empty();
+    }
+
+    /// Returns root of `x`'s component and compresses path from `x` to root
+    ///
+    /// # Arguments:
+    /// * `x` - element
+    pub fn find(&mut self, x: usize) -> usize {
+        // Find root
+        let mut root: usize = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Attach every element on the path directly to root
+        let mut x: usize = x;
+        while self.parent[x] != root {
+            (x, self.parent[x]) = (self.parent[x], root);
+        }
+
+        return root;
+    }
+
+    /// Merges components of `a` and `b`. Returns `false` if they were already merged
+    ///
+    /// # Arguments:
+    /// * `a` - first element
+    /// * `b` - second element
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // Smaller component is attached to larger one
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        return true;
+    }
+
+    /// Returns `true` if `a` and `b` are in the same component
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        return self.find(a) == self.find(b);
+    }
+
+    /// Returns size of `x`'s component
+    pub fn size(&mut self, x: usize) -> usize {
+        let root: usize = self.find(x);
+        return self.size[root];
+    }
+
+    /// Returns number of components
+    pub fn count(&self) -> usize {
+        return self.components;
+    }
+}
+
+/// Disjoint Set Union with rollback, that allows to undo unions in reverse order (for example,
+/// for offline dynamic connectivity).
+///
+/// Path compression would make undoing expensive, so only union by size is used.
+///
+/// Memory complexity - O(N + U), where U is number of unions since construction
+///
+/// Time complexity:
+/// * `find` - O(logN)
+/// * `union` - O(logN)
+/// * `rollback` - O(1) per undone union
+pub struct RollbackDsu {
+    /// `parent[i]` is parent of `i`, roots are parents of themselves
+    parent: Vec<usize>,
+    /// `size[i]` is size of component, if `i` is a root
+    size: Vec<usize>,
+    /// `components` is number of components
+    components: usize,
+    /// `history` contains roots of attached components in order of unions
+    history: Vec<usize>,
+}
+
+impl RollbackDsu {
+    /// Constructs and returns DSU with rollback of `n` single-element components
+    ///
+    /// # Arguments:
+    /// * `n` - number of elements
+    pub fn new(n: usize) -> RollbackDsu {
+        return RollbackDsu {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+            history: Vec::new(),
+        };
+    }
+
+    /// Returns number of elements
+    pub fn len(&self) -> usize {
+        return self.parent.len();
+    }
+
+    /// Returns `true` if there are no elements
+    pub fn is_empty(&self) -> bool {
+        return self.parent.is_empty();
+    }
+
+    /// Returns root of `x`'s component
+    ///
+    /// # Arguments:
+    /// * `x` - element
+    pub fn find(&self, x: usize) -> usize {
+        let mut root: usize = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        return root;
+    }
+
+    /// Merges components of `a` and `b`. Returns `false` if they were already merged, nothing is
+    /// recorded in this case
+    ///
+    /// # Arguments:
+    /// * `a` - first element
+    /// * `b` - second element
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // Smaller component is attached to larger one
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        self.history.push(b);
+        return true;
+    }
+
+    /// Returns `true` if `a` and `b` are in the same component
+    pub fn same(&self, a: usize, b: usize) -> bool {
+        return self.find(a) == self.find(b);
+    }
+
+    /// Returns size of `x`'s component
+    pub fn size(&self, x: usize) -> usize {
+        return self.size[self.find(x)];
+    }
+
+    /// Returns number of components
+    pub fn count(&self) -> usize {
+        return self.components;
+    }
+
+    /// Returns current state, which can be passed to `rollback`
+    pub fn snapshot(&self) -> usize {
+        return self.history.len();
+    }
+
+    /// Undoes every union made after `snapshot` was taken
+    ///
+    /// # Arguments:
+    /// * `snapshot` - state returned by `snapshot`
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len());
+
+        while self.history.len() > snapshot {
+            let b: usize = self.history.pop().unwrap();
+            let a: usize = self.parent[b];
+            self.size[a] -= self.size[b];
+            self.parent[b] = b;
+            self.components += 1;
+        }
+    }
+}
+
+/// Disjoint Set Union, that keeps an aggregate of every component (such as sum of weights or
+/// minimum), aggregates are combined with a monoid on union.
+///
+/// Memory complexity - O(N)
+///
+/// Time complexity (amortized):
+/// * `find` - O(α(N))
+/// * `union` - O(α(N)) and one `combine`
+pub struct DsuWithData<T: Clone, M: Monoid<T>> {
+    /// `dsu` is underlying DSU
+    dsu: Dsu,
+    /// `data[i]` is aggregate of component, if `i` is a root
+    data: Vec<T>,
+    /// `monoid` provides an associative combination function, such as addition or minimum.
+    /// Components are merged in arbitrary order, so it should be commutative
+    monoid: M,
+}
+
+/// DSU with data generic implementation.
+///
+/// Type `T` must support `Clone` for storing initial values.
+impl<T: Clone, M: Monoid<T>> DsuWithData<T, M> {
+    /// Constructs and returns DSU of single-element components with given values
+    ///
+    /// # Arguments:
+    /// * `values` - initial value of every element
+    /// * `monoid` - A monoid, which combines aggregates on union, such as `Sum` or `Min`
+    pub fn new(values: &[T], monoid: M) -> DsuWithData<T, M> {
+        return DsuWithData {
+            dsu: Dsu::new(values.len()),
+            data: values.to_vec(),
+            monoid,
+        };
+    }
+
+    /// Returns number of elements
+    pub fn len(&self) -> usize {
+        return self.dsu.len();
+    }
+
+    /// Returns `true` if there are no elements
+    pub fn is_empty(&self) -> bool {
+        return self.dsu.is_empty();
+    }
+
+    /// Returns root of `x`'s component
+    pub fn find(&mut self, x: usize) -> usize {
+        return self.dsu.find(x);
+    }
+
+    /// Merges components of `a` and `b` and their aggregates. Returns `false` if they were already
+    /// merged
+    ///
+    /// # Arguments:
+    /// * `a` - first element
+    /// * `b` - second element
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.dsu.find(a), self.dsu.find(b));
+        if !self.dsu.union(a, b) {
+            return false;
+        }
+
+        // Root of merged component is one of the old roots
+        let (root, other) = if self.dsu.find(a) == a {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.data[root] = self.monoid.combine(&self.data[root], &self.data[other]);
+        return true;
+    }
+
+    /// Returns `true` if `a` and `b` are in the same component
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        return self.dsu.same(a, b);
+    }
+
+    /// Returns size of `x`'s component
+    pub fn size(&mut self, x: usize) -> usize {
+        return self.dsu.size(x);
+    }
+
+    /// Returns number of components
+    pub fn count(&self) -> usize {
+        return self.dsu.count();
+    }
+
+    /// Returns aggregate of `x`'s component
+    pub fn data(&mut self, x: usize) -> &T {
+        let root: usize = self.dsu.find(x);
+        return &self.data[root];
+    }
+}
+
+/// Disjoint Set Union with rollback, that keeps an aggregate of every component, aggregates are
+/// combined with a monoid on union and restored on rollback.
+///
+/// Memory complexity - O(N + U), where U is number of unions since construction
+///
+/// Time complexity:
+/// * `find` - O(logN)
+/// * `union` - O(logN) and one `combine`
+/// * `rollback` - O(1) per undone union
+pub struct RollbackDsuWithData<T: Clone, M: Monoid<T>> {
+    /// `dsu` is underlying DSU with rollback
+    dsu: RollbackDsu,
+    /// `data[i]` is aggregate of component, if `i` is a root
+    data: Vec<T>,
+    /// `history` contains roots of merged components and their aggregates before union, in order
+    /// of unions
+    history: Vec<(usize, T)>,
+    /// `monoid` provides an associative combination function, such as addition or minimum.
+    /// Components are merged in arbitrary order, so it should be commutative
+    monoid: M,
+}
+
+/// DSU with rollback and data generic implementation.
+///
+/// Type `T` must support `Clone` for storing initial values and aggregates before union.
+impl<T: Clone, M: Monoid<T>> RollbackDsuWithData<T, M> {
+    /// Constructs and returns DSU with rollback of single-element components with given values
+    ///
+    /// # Arguments:
+    /// * `values` - initial value of every element
+    /// * `monoid` - A monoid, which combines aggregates on union, such as `Sum` or `Min`
+    pub fn new(values: &[T], monoid: M) -> RollbackDsuWithData<T, M> {
+        return RollbackDsuWithData {
+            dsu: RollbackDsu::new(values.len()),
+            data: values.to_vec(),
+            history: Vec::new(),
+            monoid,
+        };
+    }
+
+    /// Returns number of elements
+    pub fn len(&self) -> usize {
+        return self.dsu.len();
+    }
+
+    /// Returns `true` if there are no elements
+    pub fn is_empty(&self) -> bool {
+        return self.dsu.is_empty();
+    }
+
+    /// Returns root of `x`'s component
+    pub fn find(&self, x: usize) -> usize {
+        return self.dsu.find(x);
+    }
+
+    /// Merges components of `a` and `b` and their aggregates. Returns `false` if they were already
+    /// merged, nothing is recorded in this case
+    ///
+    /// # Arguments:
+    /// * `a` - first element
+    /// * `b` - second element
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.dsu.find(a), self.dsu.find(b));
+        if !self.dsu.union(a, b) {
+            return false;
+        }
+
+        // Root of merged component is one of the old roots
+        let (root, other) = if self.dsu.find(a) == a {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let merged: T = self.monoid.combine(&self.data[root], &self.data[other]);
+        let old: T = std::mem::replace(&mut self.data[root], merged);
+        self.history.push((root, old));
+        return true;
+    }
+
+    /// Returns `true` if `a` and `b` are in the same component
+    pub fn same(&self, a: usize, b: usize) -> bool {
+        return self.dsu.same(a, b);
+    }
+
+    /// Returns size of `x`'s component
+    pub fn size(&self, x: usize) -> usize {
+        return self.dsu.size(x);
+    }
+
+    /// Returns number of components
+    pub fn count(&self) -> usize {
+        return self.dsu.count();
+    }
+
+    /// Returns aggregate of `x`'s component
+    pub fn data(&self, x: usize) -> &T {
+        return &self.data[self.dsu.find(x)];
+    }
+
+    /// Returns current state, which can be passed to `rollback`
+    pub fn snapshot(&self) -> usize {
+        return self.history.len();
+    }
+
+    /// Undoes every union made after `snapshot` was taken and restores aggregates
+    ///
+    /// # Arguments:
+    /// * `snapshot` - state returned by `snapshot`
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len());
+
+        while self.history.len() > snapshot {
+            let (root, old) = self.history.pop().unwrap();
+            self.data[root] = old;
+        }
+        self.dsu.rollback(snapshot);
+    }
+}
@@ -0,0 +1,580 @@
+use crate::monoid::{Max, Min, Monoid, Sum};
+
+/// Coordinate type of `DynamicSegmentTree` keys
+pub trait Key: Copy + Ord {
+    /// Returns middle of `[lo, hi)` without overflow
+    fn midpoint(lo: Self, hi: Self) -> Self;
+
+    /// Returns number of keys in `[lo, hi)`
+    fn distance(lo: Self, hi: Self) -> u64;
+}
+
+impl Key for i64 {
+    fn midpoint(lo: i64, hi: i64) -> i64 {
+        return ((lo as i128 + hi as i128).div_euclid(2)) as i64;
+    }
+
+    fn distance(lo: i64, hi: i64) -> u64 {
+        return (hi as i128 - lo as i128) as u64;
+    }
+}
+
+impl Key for u64 {
+    fn midpoint(lo: u64, hi: u64) -> u64 {
+        return lo + (hi - lo) / 2;
+    }
+
+    fn distance(lo: u64, hi: u64) -> u64 {
+        return hi - lo;
+    }
+}
+
+/// Monoid, that supports adding a value to every key of a segment
+pub trait RangeAdd<T>: Monoid<T> {
+    /// Returns value of a node, which contains `len` keys, after adding `delta` to every key
+    fn add_to_segment(&self, value: &T, delta: &T, len: u64) -> T;
+
+    /// Returns a single delta, equal to adding `older` and then `newer`. It is addition, not
+    /// `combine` (which is, for example, maximum for `Max`)
+    fn compose_delta(&self, older: &T, newer: &T) -> T;
+}
+
+/// Implements `RangeAdd` of `Sum` for integer types. `delta * len` is computed in wider type `$w`
+/// and panics, if it doesn't fit into `$t`, instead of truncating `len`
+macro_rules! impl_integer_sum_range_add {
+    ($($t:ty => $w:ty),*) => {
+        $(
+            impl RangeAdd<$t> for Sum {
+                fn add_to_segment(&self, value: &$t, delta: &$t, len: u64) -> $t {
+                    let added: $t = <$w>::from(*delta)
+                        .checked_mul(<$w>::from(len))
+                        .and_then(|added| <$t>::try_from(added).ok())
+                        .expect("range addition overflows value type");
+                    return *value + added;
+                }
+
+                fn compose_delta(&self, older: &$t, newer: &$t) -> $t {
+                    return *older + *newer;
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `RangeAdd` of `Sum` for floating point types
+macro_rules! impl_float_sum_range_add {
+    ($($t:ty),*) => {
+        $(
+            impl RangeAdd<$t> for Sum {
+                fn add_to_segment(&self, value: &$t, delta: &$t, len: u64) -> $t {
+                    return *value + *delta * len as $t;
+                }
+
+                fn compose_delta(&self, older: &$t, newer: &$t) -> $t {
+                    return *older + *newer;
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `RangeAdd` of `Min` and `Max` for integer types. Result of a segment is shifted by
+/// `delta` regardless of its length. Identity (`MAX` for `Min` and `MIN` for `Max`) stands for a
+/// segment without values and is left as is
+macro_rules! impl_integer_extremum_range_add {
+    ($($t:ty),*) => {
+        $(
+            impl RangeAdd<$t> for Min {
+                fn add_to_segment(&self, value: &$t, delta: &$t, _len: u64) -> $t {
+                    if *value == self.identity() {
+                        return *value;
+                    }
+                    return *value + *delta;
+                }
+
+                fn compose_delta(&self, older: &$t, newer: &$t) -> $t {
+                    return *older + *newer;
+                }
+            }
+
+            impl RangeAdd<$t> for Max {
+                fn add_to_segment(&self, value: &$t, delta: &$t, _len: u64) -> $t {
+                    if *value == self.identity() {
+                        return *value;
+                    }
+                    return *value + *delta;
+                }
+
+                fn compose_delta(&self, older: &$t, newer: &$t) -> $t {
+                    return *older + *newer;
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `RangeAdd` of `Min` and `Max` for floating point types, identities are infinite,
+/// so they are not changed by addition
+macro_rules! impl_float_extremum_range_add {
+    ($($t:ty),*) => {
+        $(
+            impl RangeAdd<$t> for Min {
+                fn add_to_segment(&self, value: &$t, delta: &$t, _len: u64) -> $t {
+                    return *value + *delta;
+                }
+
+                fn compose_delta(&self, older: &$t, newer: &$t) -> $t {
+                    return *older + *newer;
+                }
+            }
+
+            impl RangeAdd<$t> for Max {
+                fn add_to_segment(&self, value: &$t, delta: &$t, _len: u64) -> $t {
+                    return *value + *delta;
+                }
+
+                fn compose_delta(&self, older: &$t, newer: &$t) -> $t {
+                    return *older + *newer;
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_sum_range_add!(
+    i32 => i128, i64 => i128, i128 => i128, u32 => u128, u64 => u128, u128 => u128
+);
+impl_float_sum_range_add!(f32, f64);
+impl_integer_extremum_range_add!(i32, i64, i128, u32, u64, u128);
+impl_float_extremum_range_add!(f32, f64);
+
+/// Index of absent child
+const NONE: usize = usize::MAX;
+
+/// Node of dynamic segment tree
+///
+/// # Fields:
+/// * `value` - result of `combine` for node's segment
+/// * `left` - index of left child in `nodes`, `NONE` if it is not created yet
+/// * `right` - index of right child in `nodes`, `NONE` if it is not created yet
+struct Node<T> {
+    value: T,
+    left: usize,
+    right: usize,
+}
+
+/// Dynamic (sparse) segment tree over `[lo, hi)` range of `i64` or `u64` keys, that allows to
+/// efficiently compute associative function on a segment of keys.
+///
+/// Nodes are created only when keys of their segments are set, so memory is proportional to the
+/// number of set keys, not to the size of the range. Untouched keys are equal to identity.
+/// `LazyDynamicSegmentTree` also allows to add a value to every key of a segment.
+///
+/// Described [here](https://cp-algorithms.com/data_structures/segment_tree.html#dynamic-segment-tree).
+///
+/// Memory complexity - O(QlogR), where Q is a number of updates and R is `hi - lo`
+///
+/// Time complexity:
+/// * `get` - O(logR)
+/// * `set` - O(logR)
+pub struct DynamicSegmentTree<K: Key, T: Clone, M: Monoid<T>> {
+    /// `nodes` is a vector of created nodes, `nodes[0]` is a root node
+    nodes: Vec<Node<T>>,
+    /// `lo` is the smallest key
+    lo: K,
+    /// `hi` is the bound of keys (non-inclusive)
+    hi: K,
+    /// `monoid` provides an identity element and an associative combination function, such as
+    /// addition or gcd
+    monoid: M,
+}
+
+/// Dynamic segment tree generic implementation.
+///
+/// Type `T` must support `Clone` for moving elements from nodes.
+impl<K: Key, T: Clone, M: Monoid<T>> DynamicSegmentTree<K, T, M> {
+    /// Constructs and returns dynamic segment tree over `[lo, hi)` keys, every key is equal to
+    /// identity
+    ///
+    /// # Arguments:
+    ///
+    /// * `lo` - the smallest key
+    /// * `hi` - the bound of keys (non-inclusive)
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Max`
+    pub fn new(lo: K, hi: K, monoid: M) -> DynamicSegmentTree<K, T, M> {
+        assert!(lo < hi);
+
+        let mut seg_tree: DynamicSegmentTree<K, T, M> = DynamicSegmentTree {
+            nodes: Vec::new(),
+            lo,
+            hi,
+            monoid,
+        };
+        seg_tree.__new_node();
+
+        return seg_tree;
+    }
+
+    /// Returns number of created nodes
+    pub fn nodes_count(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    /// Creates new node filled with identity and returns its index
+    fn __new_node(&mut self) -> usize {
+        self.nodes.push(Node {
+            value: self.monoid.identity(),
+            left: NONE,
+            right: NONE,
+        });
+        return self.nodes.len() - 1;
+    }
+
+    /// Returns value of the node or identity if it is absent
+    fn __value(&self, node: usize) -> T {
+        return if node == NONE {
+            self.monoid.identity()
+        } else {
+            self.nodes[node].value.clone()
+        };
+    }
+
+    /// Recomputes node's value from its children
+    fn __pull(&mut self, node: usize) {
+        let l_child: T = self.__value(self.nodes[node].left);
+        let r_child: T = self.__value(self.nodes[node].right);
+        self.nodes[node].value = self.monoid.combine(&l_child, &r_child);
+    }
+
+    /// Returns `combine` result for `[query_l, query_r)` keys
+    ///
+    /// # Arguments:
+    ///
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node's keys
+    /// * `tree_r` - right bound of node's keys (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    fn __get(&self, node: usize, tree_l: K, tree_r: K, query_l: K, query_r: K) -> T {
+        if node == NONE || tree_r <= query_l || query_r <= tree_l {
+            // Node is absent or doesn't intersect with query
+            return self.monoid.identity();
+        } else if query_l <= tree_l && tree_r <= query_r {
+            // tree_l..tree_r completely lies in query_l..query_r
+            return self.nodes[node].value.clone();
+        }
+
+        let m: K = K::midpoint(tree_l, tree_r);
+        let l_child: T = self.__get(self.nodes[node].left, tree_l, m, query_l, query_r);
+        let r_child: T = self.__get(self.nodes[node].right, m, tree_r, query_l, query_r);
+        return self.monoid.combine(&l_child, &r_child);
+    }
+
+    /// Friendly interface of `__get` with query bounds assert. Absent nodes are treated as
+    /// identity, so queries don't create nodes
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, query_l: K, query_r: K) -> T {
+        // Assert query bounds
+        assert!(self.lo <= query_l && query_l < query_r && query_r <= self.hi);
+        return self.__get(0, self.lo, self.hi, query_l, query_r);
+    }
+
+    /// Sets `key` to `new_val`, creating nodes on the path
+    ///
+    /// # Arguments:
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node's keys
+    /// * `tree_r` - right bound of node's keys (non-inclusive)
+    /// * `key` - key of element
+    /// * `new_val` - new value of element
+    fn __set(&mut self, node: usize, tree_l: K, tree_r: K, key: K, new_val: T) {
+        // Node contains one key
+        if K::distance(tree_l, tree_r) == 1 {
+            self.nodes[node].value = new_val;
+            return;
+        }
+
+        let m: K = K::midpoint(tree_l, tree_r);
+        if key < m {
+            if self.nodes[node].left == NONE {
+                self.nodes[node].left = self.__new_node();
+            }
+            self.__set(self.nodes[node].left, tree_l, m, key, new_val);
+        } else {
+            if self.nodes[node].right == NONE {
+                self.nodes[node].right = self.__new_node();
+            }
+            self.__set(self.nodes[node].right, m, tree_r, key, new_val);
+        }
+        self.__pull(node);
+    }
+
+    /// Friendly interface of `__set` with `key` assert
+    ///
+    /// # Arguments:
+    /// * `key` - key of element
+    /// * `new_val` - new value of element
+    pub fn set(&mut self, key: K, new_val: T) {
+        assert!(self.lo <= key && key < self.hi); // Asserts `key` bounds
+        self.__set(0, self.lo, self.hi, key, new_val);
+    }
+}
+
+/// Node of lazy dynamic segment tree
+///
+/// # Fields:
+/// * `value` - result of `combine` for node's segment
+/// * `pending` - delta, which is already added to `value`, but not to children
+/// * `left` - index of left child in `nodes`, `NONE` if it is not created yet
+/// * `right` - index of right child in `nodes`, `NONE` if it is not created yet
+struct LazyNode<T> {
+    value: T,
+    pending: Option<T>,
+    left: usize,
+    right: usize,
+}
+
+/// Dynamic segment tree with [lazy propagation](https://cp-algorithms.com/data_structures/segment_tree.html#range-updates-lazy-propagation),
+/// that also allows to add a value to every key of a segment. Monoid must implement `RangeAdd`.
+///
+/// Memory complexity - O(QlogR), where Q is a number of updates and R is `hi - lo`
+///
+/// Time complexity:
+/// * `get` - O(logR)
+/// * `set` - O(logR)
+/// * `add` - O(logR)
+pub struct LazyDynamicSegmentTree<K: Key, T: Clone, M: RangeAdd<T>> {
+    /// `nodes` is a vector of created nodes, `nodes[0]` is a root node
+    nodes: Vec<LazyNode<T>>,
+    /// `lo` is the smallest key
+    lo: K,
+    /// `hi` is the bound of keys (non-inclusive)
+    hi: K,
+    /// `monoid` provides an identity element, an associative combination function and addition
+    /// of a value to a segment, such as `Sum` or `Max`
+    monoid: M,
+}
+
+/// Lazy dynamic segment tree generic implementation.
+///
+/// Type `T` must support `Clone` for moving elements from nodes and pushing deltas to children.
+impl<K: Key, T: Clone, M: RangeAdd<T>> LazyDynamicSegmentTree<K, T, M> {
+    /// Constructs and returns lazy dynamic segment tree over `[lo, hi)` keys, every key is equal
+    /// to identity
+    ///
+    /// # Arguments:
+    ///
+    /// * `lo` - the smallest key
+    /// * `hi` - the bound of keys (non-inclusive)
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum`
+    pub fn new(lo: K, hi: K, monoid: M) -> LazyDynamicSegmentTree<K, T, M> {
+        assert!(lo < hi);
+
+        let mut seg_tree: LazyDynamicSegmentTree<K, T, M> = LazyDynamicSegmentTree {
+            nodes: Vec::new(),
+            lo,
+            hi,
+            monoid,
+        };
+        seg_tree.__new_node();
+
+        return seg_tree;
+    }
+
+    /// Returns number of created nodes
+    pub fn nodes_count(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    /// Creates new node filled with identity and returns its index
+    fn __new_node(&mut self) -> usize {
+        self.nodes.push(LazyNode {
+            value: self.monoid.identity(),
+            pending: None,
+            left: NONE,
+            right: NONE,
+        });
+        return self.nodes.len() - 1;
+    }
+
+    /// Creates absent children of the node
+    fn __create_children(&mut self, node: usize) {
+        if self.nodes[node].left == NONE {
+            self.nodes[node].left = self.__new_node();
+        }
+        if self.nodes[node].right == NONE {
+            self.nodes[node].right = self.__new_node();
+        }
+    }
+
+    /// Returns value of the node or identity if it is absent
+    fn __value(&self, node: usize) -> T {
+        return if node == NONE {
+            self.monoid.identity()
+        } else {
+            self.nodes[node].value.clone()
+        };
+    }
+
+    /// Recomputes node's value from its children
+    fn __pull(&mut self, node: usize) {
+        let l_child: T = self.__value(self.nodes[node].left);
+        let r_child: T = self.__value(self.nodes[node].right);
+        self.nodes[node].value = self.monoid.combine(&l_child, &r_child);
+    }
+
+    /// Adds `delta` to every key of the node, which contains `len` keys, and remembers it as
+    /// pending
+    fn __apply(&mut self, node: usize, len: u64, delta: &T) {
+        let node_ref: &mut LazyNode<T> = &mut self.nodes[node];
+        node_ref.value = M::add_to_segment(&self.monoid, &node_ref.value, delta, len);
+        node_ref.pending = match &node_ref.pending {
+            Some(pending) => Some(self.monoid.compose_delta(pending, delta)),
+            None => Some(delta.clone()),
+        };
+    }
+
+    /// Pushes pending delta of the node to its children, creating them if needed
+    ///
+    /// # Arguments:
+    ///
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node's keys
+    /// * `tree_r` - right bound of node's keys (non-inclusive)
+    fn __push(&mut self, node: usize, tree_l: K, tree_r: K) {
+        if let Some(delta) = self.nodes[node].pending.take() {
+            self.__create_children(node);
+            let m: K = K::midpoint(tree_l, tree_r);
+            self.__apply(self.nodes[node].left, K::distance(tree_l, m), &delta);
+            self.__apply(self.nodes[node].right, K::distance(m, tree_r), &delta);
+        }
+    }
+
+    /// Returns `combine` result for `[query_l, query_r)` keys. Pending delta of a partially
+    /// covered node isn't pushed, it is added to the result of its children instead
+    ///
+    /// # Arguments:
+    ///
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node's keys
+    /// * `tree_r` - right bound of node's keys (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    fn __get(&self, node: usize, tree_l: K, tree_r: K, query_l: K, query_r: K) -> T {
+        if node == NONE || tree_r <= query_l || query_r <= tree_l {
+            // Node is absent or doesn't intersect with query
+            return self.monoid.identity();
+        } else if query_l <= tree_l && tree_r <= query_r {
+            // tree_l..tree_r completely lies in query_l..query_r
+            return self.nodes[node].value.clone();
+        }
+
+        let m: K = K::midpoint(tree_l, tree_r);
+        let l_child: T = self.__get(self.nodes[node].left, tree_l, m, query_l, query_r);
+        let r_child: T = self.__get(self.nodes[node].right, m, tree_r, query_l, query_r);
+        let result: T = self.monoid.combine(&l_child, &r_child);
+        return match &self.nodes[node].pending {
+            Some(delta) => {
+                let len: u64 = K::distance(tree_l.max(query_l), tree_r.min(query_r));
+                M::add_to_segment(&self.monoid, &result, delta, len)
+            }
+            None => result,
+        };
+    }
+
+    /// Friendly interface of `__get` with query bounds assert. Absent nodes are treated as
+    /// identity, so queries don't create nodes
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, query_l: K, query_r: K) -> T {
+        // Assert query bounds
+        assert!(self.lo <= query_l && query_l < query_r && query_r <= self.hi);
+        return self.__get(0, self.lo, self.hi, query_l, query_r);
+    }
+
+    /// Sets `key` to `new_val`, creating nodes on the path
+    ///
+    /// # Arguments:
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node's keys
+    /// * `tree_r` - right bound of node's keys (non-inclusive)
+    /// * `key` - key of element
+    /// * `new_val` - new value of element
+    fn __set(&mut self, node: usize, tree_l: K, tree_r: K, key: K, new_val: T) {
+        // Node contains one key
+        if K::distance(tree_l, tree_r) == 1 {
+            self.nodes[node].value = new_val;
+            self.nodes[node].pending = None;
+            return;
+        }
+
+        self.__push(node, tree_l, tree_r);
+        let m: K = K::midpoint(tree_l, tree_r);
+        if key < m {
+            if self.nodes[node].left == NONE {
+                self.nodes[node].left = self.__new_node();
+            }
+            self.__set(self.nodes[node].left, tree_l, m, key, new_val);
+        } else {
+            if self.nodes[node].right == NONE {
+                self.nodes[node].right = self.__new_node();
+            }
+            self.__set(self.nodes[node].right, m, tree_r, key, new_val);
+        }
+        self.__pull(node);
+    }
+
+    /// Friendly interface of `__set` with `key` assert
+    ///
+    /// # Arguments:
+    /// * `key` - key of element
+    /// * `new_val` - new value of element
+    pub fn set(&mut self, key: K, new_val: T) {
+        assert!(self.lo <= key && key < self.hi); // Asserts `key` bounds
+        self.__set(0, self.lo, self.hi, key, new_val);
+    }
+
+    /// Adds `delta` to every key in `[query_l, query_r)`
+    ///
+    /// # Arguments:
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node's keys
+    /// * `tree_r` - right bound of node's keys (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    /// * `delta` - value to add
+    fn __add(&mut self, node: usize, tree_l: K, tree_r: K, query_l: K, query_r: K, delta: &T) {
+        if tree_r <= query_l || query_r <= tree_l {
+            // tree_l..tree_r doesn't intersect with query_l..query_r
+            return;
+        } else if query_l <= tree_l && tree_r <= query_r {
+            // tree_l..tree_r completely lies in query_l..query_r, so delta is stored here
+            self.__apply(node, K::distance(tree_l, tree_r), delta);
+            return;
+        }
+
+        self.__push(node, tree_l, tree_r);
+        self.__create_children(node);
+        let m: K = K::midpoint(tree_l, tree_r);
+        self.__add(self.nodes[node].left, tree_l, m, query_l, query_r, delta);
+        self.__add(self.nodes[node].right, m, tree_r, query_l, query_r, delta);
+        self.__pull(node);
+    }
+
+    /// Friendly interface of `__add` with query bounds assert
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    /// * `delta` - value to add
+    pub fn add(&mut self, query_l: K, query_r: K, delta: T) {
+        // Assert query bounds
+        assert!(self.lo <= query_l && query_l < query_r && query_r <= self.hi);
+        self.__add(0, self.lo, self.hi, query_l, query_r, &delta);
+    }
+}
@@ -0,0 +1,375 @@
+use crate::monoid::Group;
+
+/// Returns the lowest set bit of `i`
+fn lowest_bit(i: usize) -> usize {
+    return i & i.wrapping_neg();
+}
+
+/// Combines `a` with itself `k` times (`k * a` for addition) using
+/// [binary exponentiation](https://en.wikipedia.org/wiki/Exponentiation_by_squaring) idea, so
+/// only O(logK) combinations are made
+fn scale<T: Clone, G: Group<T>>(group: &G, a: &T, k: usize) -> T {
+    let mut result: T = group.identity();
+    let mut power: T = a.clone();
+    let mut k: usize = k;
+    while k > 0 {
+        if k % 2 == 1 {
+            result = group.combine(&result, &power);
+        }
+        k /= 2;
+        if k == 0 {
+            // `power` isn't needed anymore, doubling it may overflow
+            break;
+        }
+        power = group.combine(&power, &power);
+    }
+    return result;
+}
+
+/// [Fenwick tree](https://en.wikipedia.org/wiki/Fenwick_tree) (binary indexed tree), that allows
+/// to add to an element and to compute prefix results of a commutative group operation, such as
+/// sum or xor.
+///
+/// Described [here](https://cp-algorithms.com/data_structures/fenwick.html).
+///
+/// Memory complexity - O(N), exactly `N + 1` elements
+///
+/// Time complexity:
+/// * `add` - O(logN)
+/// * `prefix`, `get` - O(logN)
+/// * `lower_bound` - O(logN)
+pub struct Fenwick<T: Clone, G: Group<T>> {
+    /// `tree` is a 1-indexed vector, `tree[i]` contains result for `v[i - lowest_bit(i)..i]`
+    tree: Vec<T>,
+    /// `n` is initial vector's size
+    n: usize,
+    /// `group` provides an identity element, an inverse element and a commutative combination
+    /// function, such as addition or xor
+    group: G,
+}
+
+/// Fenwick tree generic implementation.
+///
+/// Type `T` must support `Clone` for creating initial tree filled with identities.
+impl<T: Clone, G: Group<T>> Fenwick<T, G> {
+    /// Constructs and returns Fenwick tree of `n` identity elements
+    ///
+    /// # Arguments:
+    /// * `n` - size of array
+    /// * `group` - A group, which will be computed on query, such as `Sum` or `Xor`
+    pub fn new(n: usize, group: G) -> Fenwick<T, G> {
+        return Fenwick {
+            tree: vec![group.identity(); n + 1],
+            n,
+            group,
+        };
+    }
+
+    /// Constructs and returns Fenwick tree based on given vector in O(N)
+    ///
+    /// # Arguments:
+    /// * `v` - An initial vector
+    /// * `group` - A group, which will be computed on query, such as `Sum` or `Xor`
+    pub fn from_slice(v: &[T], group: G) -> Fenwick<T, G> {
+        let n: usize = v.len();
+        let mut tree: Vec<T> = Vec::with_capacity(n + 1);
+        tree.push(group.identity());
+        tree.extend_from_slice(v);
+
+        // Every node passes its result to the next node covering it
+        for i in 1..=n {
+            let parent: usize = i + lowest_bit(i);
+            if parent <= n {
+                tree[parent] = group.combine(&tree[parent], &tree[i]);
+            }
+        }
+
+        return Fenwick { tree, n, group };
+    }
+
+    /// Returns initial vector's size
+    pub fn len(&self) -> usize {
+        return self.n;
+    }
+
+    /// Returns `true` if initial vector is empty
+    pub fn is_empty(&self) -> bool {
+        return self.n == 0;
+    }
+
+    /// Combines `i`-th element with `delta`
+    ///
+    /// # Arguments:
+    /// * `i` - index of element in initial array
+    /// * `delta` - value to combine with, for example, value to add
+    pub fn add(&mut self, i: usize, delta: &T) {
+        assert!(i < self.n); // Asserts `i` bounds
+
+        let mut tree_id: usize = i + 1;
+        while tree_id <= self.n {
+            self.tree[tree_id] = self.group.combine(&self.tree[tree_id], delta);
+            tree_id += lowest_bit(tree_id);
+        }
+    }
+
+    /// Returns `combine` result for `v[0..r]`
+    ///
+    /// # Arguments:
+    /// * `r` - right bound of prefix (non-inclusive)
+    pub fn prefix(&self, r: usize) -> T {
+        assert!(r <= self.n); // Asserts `r` bounds
+
+        let mut result: T = self.group.identity();
+        let mut tree_id: usize = r;
+        while tree_id > 0 {
+            result = self.group.combine(&self.tree[tree_id], &result);
+            tree_id -= lowest_bit(tree_id);
+        }
+        return result;
+    }
+
+    /// Returns `combine` result for `v[query_l..query_r]` as a difference of two prefixes
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, query_l: usize, query_r: usize) -> T {
+        // Assert query bounds
+        assert!(query_l <= query_r && query_r <= self.n);
+        let prefix_l: T = self.prefix(query_l);
+        return self
+            .group
+            .combine(&self.group.inverse(&prefix_l), &self.prefix(query_r));
+    }
+}
+
+/// Binary search by cumulative weight
+impl<T: Clone + PartialOrd, G: Group<T>> Fenwick<T, G> {
+    /// Returns the smallest `i` such `combine(v[0..=i]) >= target`, or `n` if there is no such
+    /// `i`. Prefix results must be non-decreasing (for example, sum of non-negative weights).
+    ///
+    /// # Arguments:
+    /// * `target` - cumulative weight to search
+    pub fn lower_bound(&self, target: &T) -> usize {
+        // Descend by powers of two, keeping result of `v[0..pos]` less than `target`
+        let mut pos: usize = 0;
+        let mut acc: T = self.group.identity();
+        let mut step: usize = if self.n == 0 { 0 } else { 1 << self.n.ilog2() };
+        while step > 0 {
+            if pos + step <= self.n {
+                let combined: T = self.group.combine(&acc, &self.tree[pos + step]);
+                if combined < *target {
+                    pos += step;
+                    acc = combined;
+                }
+            }
+            step /= 2;
+        }
+        return pos;
+    }
+}
+
+/// Fenwick tree with range updates and range queries, built on two Fenwick trees.
+///
+/// Adding `d` to `v[l..r]` is stored as `d` added at `l` and removed at `r` in the first tree
+/// and `d * l` added at `l` and `d * r` removed at `r` in the second one. Then
+/// `combine(v[0..p]) = p * first.prefix(p) - second.prefix(p)`.
+///
+/// Memory complexity - O(N)
+///
+/// Time complexity:
+/// * `add` - O(logN)
+/// * `prefix`, `get` - O(logN)
+pub struct RangeFenwick<T: Clone, G: Group<T>> {
+    /// `deltas` contains added values
+    deltas: Fenwick<T, G>,
+    /// `corrections` contains added values, multiplied by their positions
+    corrections: Fenwick<T, G>,
+    /// `n` is initial vector's size
+    n: usize,
+}
+
+/// Range Fenwick tree generic implementation.
+///
+/// Type `G` must support `Clone` to be shared by two Fenwick trees.
+impl<T: Clone, G: Group<T> + Clone> RangeFenwick<T, G> {
+    /// Constructs and returns range Fenwick tree of `n` identity elements
+    ///
+    /// # Arguments:
+    /// * `n` - size of array
+    /// * `group` - A group, which will be computed on query, such as `Sum`
+    pub fn new(n: usize, group: G) -> RangeFenwick<T, G> {
+        return RangeFenwick {
+            deltas: Fenwick::new(n + 1, group.clone()),
+            corrections: Fenwick::new(n + 1, group),
+            n,
+        };
+    }
+
+    /// Constructs and returns range Fenwick tree based on given vector
+    ///
+    /// # Arguments:
+    /// * `v` - An initial vector
+    /// * `group` - A group, which will be computed on query, such as `Sum`
+    pub fn from_slice(v: &[T], group: G) -> RangeFenwick<T, G> {
+        let mut range_fenwick: RangeFenwick<T, G> = RangeFenwick::new(v.len(), group);
+        for (i, el) in v.iter().enumerate() {
+            range_fenwick.add(i, i + 1, el);
+        }
+        return range_fenwick;
+    }
+
+    /// Combines every element of `v[query_l..query_r]` with `delta`
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    /// * `delta` - value to combine with, for example, value to add
+    pub fn add(&mut self, query_l: usize, query_r: usize, delta: &T) {
+        // Assert query bounds
+        assert!(query_l <= query_r && query_r <= self.n);
+
+        let group: &G = &self.deltas.group;
+        let inverse_delta: T = group.inverse(delta);
+        let l_correction: T = scale(group, delta, query_l);
+        let r_correction: T = group.inverse(&scale(group, delta, query_r));
+
+        self.deltas.add(query_l, delta);
+        self.deltas.add(query_r, &inverse_delta);
+        self.corrections.add(query_l, &l_correction);
+        self.corrections.add(query_r, &r_correction);
+    }
+
+    /// Returns `combine` result for `v[0..r]`
+    ///
+    /// # Arguments:
+    /// * `r` - right bound of prefix (non-inclusive)
+    pub fn prefix(&self, r: usize) -> T {
+        assert!(r <= self.n); // Asserts `r` bounds
+
+        let group: &G = &self.deltas.group;
+        let scaled: T = scale(group, &self.deltas.prefix(r), r);
+        return group.combine(&scaled, &group.inverse(&self.corrections.prefix(r)));
+    }
+
+    /// Returns `combine` result for `v[query_l..query_r]`
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, query_l: usize, query_r: usize) -> T {
+        // Assert query bounds
+        assert!(query_l <= query_r && query_r <= self.n);
+
+        let group: &G = &self.deltas.group;
+        let prefix_l: T = self.prefix(query_l);
+        return group.combine(&group.inverse(&prefix_l), &self.prefix(query_r));
+    }
+}
+
+/// Two-dimensional Fenwick tree, that allows to add to an element and to compute result of a
+/// commutative group operation on a rectangle.
+///
+/// Memory complexity - O(NM), where N is number of rows and M is number of columns
+///
+/// Time complexity:
+/// * `add` - O(logN * logM)
+/// * `prefix`, `get` - O(logN * logM)
+pub struct Fenwick2D<T: Clone, G: Group<T>> {
+    /// `tree` is a 1-indexed (in both dimensions) vector of rows
+    tree: Vec<Vec<T>>,
+    /// `rows` is number of rows
+    rows: usize,
+    /// `cols` is number of columns
+    cols: usize,
+    /// `group` provides an identity element, an inverse element and a commutative combination
+    /// function, such as addition or xor
+    group: G,
+}
+
+/// Two-dimensional Fenwick tree generic implementation.
+impl<T: Clone, G: Group<T>> Fenwick2D<T, G> {
+    /// Constructs and returns 2D Fenwick tree of `rows` x `cols` identity elements
+    ///
+    /// # Arguments:
+    /// * `rows` - number of rows
+    /// * `cols` - number of columns
+    /// * `group` - A group, which will be computed on query, such as `Sum` or `Xor`
+    pub fn new(rows: usize, cols: usize, group: G) -> Fenwick2D<T, G> {
+        return Fenwick2D {
+            tree: vec![vec![group.identity(); cols + 1]; rows + 1],
+            rows,
+            cols,
+            group,
+        };
+    }
+
+    /// Returns number of rows and number of columns
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.cols);
+    }
+
+    /// Combines element in row `r` and column `c` with `delta`
+    ///
+    /// # Arguments:
+    /// * `r` - row of element
+    /// * `c` - column of element
+    /// * `delta` - value to combine with, for example, value to add
+    pub fn add(&mut self, r: usize, c: usize, delta: &T) {
+        assert!(r < self.rows && c < self.cols); // Asserts `r` and `c` bounds
+
+        let mut row_id: usize = r + 1;
+        while row_id <= self.rows {
+            let mut col_id: usize = c + 1;
+            while col_id <= self.cols {
+                self.tree[row_id][col_id] = self.group.combine(&self.tree[row_id][col_id], delta);
+                col_id += lowest_bit(col_id);
+            }
+            row_id += lowest_bit(row_id);
+        }
+    }
+
+    /// Returns `combine` result for rectangle of rows `0..r` and columns `0..c`
+    ///
+    /// # Arguments:
+    /// * `r` - bottom bound of rectangle (non-inclusive)
+    /// * `c` - right bound of rectangle (non-inclusive)
+    pub fn prefix(&self, r: usize, c: usize) -> T {
+        assert!(r <= self.rows && c <= self.cols); // Asserts `r` and `c` bounds
+
+        let mut result: T = self.group.identity();
+        let mut row_id: usize = r;
+        while row_id > 0 {
+            let mut col_id: usize = c;
+            while col_id > 0 {
+                result = self.group.combine(&result, &self.tree[row_id][col_id]);
+                col_id -= lowest_bit(col_id);
+            }
+            row_id -= lowest_bit(row_id);
+        }
+        return result;
+    }
+
+    /// Returns `combine` result for rectangle with top left corner `(r1, c1)` and bottom right
+    /// corner `(r2, c2)` (non-inclusive), i.e. for rows `r1..r2` and columns `c1..c2`
+    ///
+    /// # Arguments:
+    /// * `(r1, c1)` - top left corner of query
+    /// * `(r2, c2)` - bottom right corner of query (non-inclusive)
+    pub fn get(&self, (r1, c1): (usize, usize), (r2, c2): (usize, usize)) -> T {
+        // Assert query bounds
+        assert!(r1 <= r2 && r2 <= self.rows);
+        assert!(c1 <= c2 && c2 <= self.cols);
+
+        // Inclusion-exclusion of four prefix rectangles
+        let included: T = self
+            .group
+            .combine(&self.prefix(r2, c2), &self.prefix(r1, c1));
+        let excluded: T = self
+            .group
+            .combine(&self.prefix(r1, c2), &self.prefix(r2, c1));
+        return self
+            .group
+            .combine(&included, &self.group.inverse(&excluded));
+    }
+}
@@ -0,0 +1,188 @@
+/// Marks handles, which are not in the heap
+const NONE: usize = usize::MAX;
+
+/// Indexed [binary heap](https://en.wikipedia.org/wiki/Binary_heap) (min-heap), that allows to
+/// change or remove any element by its handle, returned by `push`. Use
+/// [`Reverse`](https://doc.rust-lang.org/std/cmp/struct.Reverse.html) for a max-heap.
+///
+/// Handles are indices of pushed elements (`0` for the first one, `1` for the second and so on)
+/// and are never reused, so they can be stored as is, for example, per vertex in Dijkstra's
+/// algorithm.
+///
+/// Memory complexity - O(P), where P is number of pushes
+///
+/// Time complexity:
+/// * `push` - O(logN)
+/// * `pop` - O(logN)
+/// * `peek` - O(1)
+/// * `decrease_key` - O(logN)
+/// * `remove` - O(logN)
+pub struct IndexedHeap<T: Ord> {
+    /// `heap` contains handles in heap order, `values[heap[i]]` is not greater than values of
+    /// children `2i + 1` and `2i + 2`
+    heap: Vec<usize>,
+    /// `pos[h]` is position of handle `h` in `heap` or `NONE`, if it was popped or removed
+    pos: Vec<usize>,
+    /// `values[h]` is value of handle `h`, if it is in the heap
+    values: Vec<Option<T>>,
+}
+
+impl<T: Ord> Default for IndexedHeap<T> {
+    fn default() -> Self {
+        return IndexedHeap::new();
+    }
+}
+
+/// Indexed heap generic implementation.
+///
+/// Type `T` must support `Ord` for comparing elements.
+impl<T: Ord> IndexedHeap<T> {
+    /// Constructs and returns empty indexed heap
+    pub fn new() -> IndexedHeap<T> {
+        return IndexedHeap {
+            heap: Vec::new(),
+            pos: Vec::new(),
+            values: Vec::new(),
+        };
+    }
+
+    /// Returns number of elements in the heap
+    pub fn len(&self) -> usize {
+        return self.heap.len();
+    }
+
+    /// Returns `true` if the heap is empty
+    pub fn is_empty(&self) -> bool {
+        return self.heap.is_empty();
+    }
+
+    /// Returns `true` if element with handle `handle` is in the heap
+    pub fn contains(&self, handle: usize) -> bool {
+        return handle < self.pos.len() && self.pos[handle] != NONE;
+    }
+
+    /// Returns value of element with handle `handle` or `None`, if it is not in the heap
+    pub fn get(&self, handle: usize) -> Option<&T> {
+        return self.values.get(handle).and_then(|value| value.as_ref());
+    }
+
+    /// Returns value of heap element at position `i`
+    fn __value(&self, i: usize) -> &T {
+        return self.values[self.heap[i]].as_ref().unwrap();
+    }
+
+    /// Swaps heap elements at positions `i` and `j`
+    fn __swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.pos[self.heap[i]] = i;
+        self.pos[self.heap[j]] = j;
+    }
+
+    /// Moves heap element at position `i` up, while it is less than its parent
+    fn __sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent: usize = (i - 1) / 2;
+            if self.__value(i) >= self.__value(parent) {
+                return;
+            }
+            self.__swap(i, parent);
+            i = parent;
+        }
+    }
+
+    /// Moves heap element at position `i` down, while it is greater than any of its children
+    fn __sift_down(&mut self, mut i: usize) {
+        let n: usize = self.heap.len();
+        loop {
+            let (l, r): (usize, usize) = (2 * i + 1, 2 * i + 2);
+            let mut smallest: usize = i;
+            if l < n && self.__value(l) < self.__value(smallest) {
+                smallest = l;
+            }
+            if r < n && self.__value(r) < self.__value(smallest) {
+                smallest = r;
+            }
+            if smallest == i {
+                return;
+            }
+            self.__swap(i, smallest);
+            i = smallest;
+        }
+    }
+
+    /// Removes heap element at position `i` and returns its value
+    fn __remove_at(&mut self, i: usize) -> T {
+        let last: usize = self.heap.len() - 1;
+        self.__swap(i, last);
+        let handle: usize = self.heap.pop().unwrap();
+        self.pos[handle] = NONE;
+
+        // Former last element may be both less than its new parent and greater than its children
+        if i < last {
+            let moved: usize = self.heap[i];
+            self.__sift_up(i);
+            self.__sift_down(self.pos[moved]);
+        }
+        return self.values[handle].take().unwrap();
+    }
+
+    /// Adds element to the heap and returns its handle
+    ///
+    /// # Arguments:
+    /// * `value` - value of new element
+    pub fn push(&mut self, value: T) -> usize {
+        let handle: usize = self.values.len();
+        self.values.push(Some(value));
+        self.pos.push(self.heap.len());
+        self.heap.push(handle);
+        self.__sift_up(self.heap.len() - 1);
+        return handle;
+    }
+
+    /// Returns the smallest element or `None`, if the heap is empty
+    pub fn peek(&self) -> Option<&T> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        return Some(self.__value(0));
+    }
+
+    /// Returns handle of the smallest element or `None`, if the heap is empty
+    pub fn peek_handle(&self) -> Option<usize> {
+        return self.heap.first().copied();
+    }
+
+    /// Removes and returns the smallest element or `None`, if the heap is empty
+    pub fn pop(&mut self) -> Option<T> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        return Some(self.__remove_at(0));
+    }
+
+    /// Decreases value of element with handle `handle`. New value must not be greater than
+    /// current one
+    ///
+    /// # Arguments:
+    /// * `handle` - handle of element, returned by `push`
+    /// * `new_val` - new value of element
+    pub fn decrease_key(&mut self, handle: usize, new_val: T) {
+        assert!(self.contains(handle), "element is not in the heap");
+        assert!(
+            &new_val <= self.values[handle].as_ref().unwrap(),
+            "new value is greater than current one"
+        );
+
+        self.values[handle] = Some(new_val);
+        self.__sift_up(self.pos[handle]);
+    }
+
+    /// Removes element with handle `handle` from the heap and returns its value
+    ///
+    /// # Arguments:
+    /// * `handle` - handle of element, returned by `push`
+    pub fn remove(&mut self, handle: usize) -> T {
+        assert!(self.contains(handle), "element is not in the heap");
+        return self.__remove_at(self.pos[handle]);
+    }
+}
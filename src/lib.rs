@@ -1,12 +1,37 @@
 #![crate_name = "rust_aads"]
+//! Algorithms and data structures, used in computer science and competitive programming.
+//!
+//! Every module can be used directly (for example, `rust_aads::segtree::SegmentTree`), core types
+//! and traits are also re-exported by `prelude`:
+//!
+//! ```
+//! use rust_aads::prelude::*;
+//!
+//! let mut segtree = SegmentTree::new(&[1, 2, 3, 4], Sum);
+//! segtree.set(0, 5);
+//! assert_eq!(segtree.get(0, 3), 10);
+//! ```
+// Explicit `return` statements are the code style of this crate
+#![allow(clippy::needless_return)]
 
-mod algebra;
-mod segtree;
-mod sortings;
+pub mod algebra;
+pub mod dsu;
+pub mod dynamic_segtree;
+pub mod fenwick;
+pub mod heap;
+pub mod monoid;
+pub mod persistent_segtree;
+pub mod prelude;
+pub mod segtree;
+pub mod segtree_2d;
+pub mod sort_stats;
+pub mod sortings;
+pub mod sparse_table;
 
 #[cfg(test)]
 mod segment_tree_tests {
     use super::*;
+    use monoid::{FnMonoid, Max, Sum};
     use rand::Rng;
     use segtree::SegmentTree;
 
@@ -17,14 +42,14 @@ mod segment_tree_tests {
 
     /// Naive iterative implementation of computing function on segment
     fn compute_function_on_segment<T: Copy>(
-        v: &Vec<T>,
+        v: &[T],
         func: fn(T, T) -> T,
         l_idx: usize,
         r_idx: usize,
     ) -> T {
         let mut result: T = v[l_idx];
-        for i in l_idx + 1..r_idx {
-            result = func(result, v[i]);
+        for &el in &v[l_idx + 1..r_idx] {
+            result = func(result, el);
         }
         return result;
     }
@@ -34,7 +59,7 @@ mod segment_tree_tests {
     fn basic_get_add_test() {
         let v: Vec<i32> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
         let n = v.len();
-        let segtree: SegmentTree<i32> = SegmentTree::new(&v, 0, add_i32s);
+        let segtree: SegmentTree<i32, Sum> = SegmentTree::new(&v, Sum);
 
         for l_idx in 0..n {
             for r_idx in l_idx + 1..=n {
@@ -52,7 +77,7 @@ mod segment_tree_tests {
 
         let n: usize = 1000;
         let v: Vec<i32> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
-        let segtree: SegmentTree<i32> = SegmentTree::new(&v, 0, add_i32s);
+        let segtree: SegmentTree<i32, Sum> = SegmentTree::new(&v, Sum);
 
         for l_idx in 0..n {
             for r_idx in l_idx + 1..=n {
@@ -70,7 +95,7 @@ mod segment_tree_tests {
         let n: usize = 100;
         // Generate n random elements in range -10..10
         let mut v: Vec<i32> = (0..n).map(|_| rng.gen_range(-10..10)).collect();
-        let mut segtree: SegmentTree<i32> = SegmentTree::new(&v, 0, add_i32s);
+        let mut segtree: SegmentTree<i32, Sum> = SegmentTree::new(&v, Sum);
 
         for idx_to_change in 0..n {
             let new_val = rng.gen_range(-10..10);
@@ -86,63 +111,2077 @@ mod segment_tree_tests {
             }
         }
     }
+
+    #[test]
+    /// Maximum of non-negative numbers with identity `0`, where real values are equal to identity
+    fn max_with_zeros_test() {
+        let v: Vec<u32> = vec![0, 0, 3, 0, 1, 0];
+        let mut segtree: SegmentTree<u32, Max> = SegmentTree::new(&v, Max);
+        assert_eq!(segtree.get(0, 2), 0);
+        assert_eq!(segtree.get(1, 4), 3);
+        segtree.set(2, 0);
+        assert_eq!(segtree.get(0, 6), 1);
+    }
+
+    #[test]
+    /// Non-commutative and non-`Copy` monoid: concatenation of strings
+    fn string_concatenation_test() {
+        let v: Vec<String> = ["a", "b", "c", "d", "e"].iter().map(|s| s.to_string()).collect();
+        let concat = FnMonoid::new(String::new(), |a: &String, b: &String| a.clone() + b);
+        let mut segtree = SegmentTree::new(&v, concat);
+        assert_eq!(segtree.get(0, 5), "abcde");
+        assert_eq!(segtree.get(1, 4), "bcd");
+        segtree.set(2, String::from("xyz"));
+        assert_eq!(segtree.get(1, 4), "bxyzd");
+    }
+
+    #[test]
+    /// Closure, that captures modulo, on random 100-element array
+    fn random_100_u64s_modular_sum_test() {
+        let mut rng = rand::thread_rng();
+        let modulo: u64 = 1_000_000_007;
+        let n: usize = 100;
+        let v: Vec<u64> = (0..n).map(|_| rng.gen_range(0..modulo)).collect();
+        let segtree = SegmentTree::new(&v, FnMonoid::new(0, |a: &u64, b: &u64| (a + b) % modulo));
+
+        for l_idx in 0..n {
+            for r_idx in l_idx + 1..=n {
+                let sum_correct: u64 = v[l_idx..r_idx].iter().sum::<u64>() % modulo;
+                assert_eq!(segtree.get(l_idx, r_idx), sum_correct);
+            }
+        }
+    }
+
+    #[test]
+    /// Find the first position, where prefix sum exceeds budget, on random 200-element array
+    fn random_200_u32s_max_right_sum_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 200;
+        let v: Vec<u32> = (0..n).map(|_| rng.gen_range(0..100)).collect();
+        let segtree: SegmentTree<u32, Sum> = SegmentTree::new(&v, Sum);
+
+        for l_idx in 0..=n {
+            let budget: u32 = rng.gen_range(0..2000);
+            // Naive: extend segment while its sum fits into budget
+            let mut r_correct = l_idx;
+            let mut sum: u32 = 0;
+            while r_correct < n && sum + v[r_correct] <= budget {
+                sum += v[r_correct];
+                r_correct += 1;
+            }
+            assert_eq!(segtree.max_right(l_idx, |&s| s <= budget), r_correct);
+        }
+    }
+
+    #[test]
+    /// Find the first element greater than `x` and the last one to the left of `r`
+    fn random_200_i32s_max_right_min_left_max_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 200;
+        let v: Vec<i32> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
+        let segtree: SegmentTree<i32, Max> = SegmentTree::new(&v, Max);
+
+        for idx in 0..=n {
+            let x: i32 = rng.gen_range(-1000..1000);
+            let r_correct = (idx..n).find(|&i| v[i] > x).unwrap_or(n);
+            assert_eq!(segtree.max_right(idx, |&m| m <= x), r_correct);
+
+            let l_correct = (0..idx).rev().find(|&i| v[i] > x).map_or(0, |i| i + 1);
+            assert_eq!(segtree.min_left(idx, |&m| m <= x), l_correct);
+        }
+    }
+}
+
+#[cfg(test)]
+mod monoid_tests {
+    use super::*;
+    use monoid::{Gcd, Monoid, Product, Xor};
+    use rand::Rng;
+    use segtree::SegmentTree;
+
+    /// Compute GCD of absolute values with Euclidean algorithm
+    fn naive_gcd(a: i64, b: i64) -> i64 {
+        let (mut a, mut b) = (a.abs(), b.abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        return a;
+    }
+
+    #[test]
+    /// GCD of signed numbers is non-negative (except for `2^(bits - 1)`) and doesn't overflow on
+    /// `MIN`
+    fn signed_gcd_edge_cases_test() {
+        assert_eq!(Gcd.combine(&-12i32, &18i32), 6);
+        assert_eq!(Gcd.combine(&12i32, &-18i32), 6);
+        assert_eq!(Gcd.combine(&0i32, &-7i32), 7);
+        assert_eq!(Gcd.combine(&i32::MIN, &-1i32), 1);
+        assert_eq!(Gcd.combine(&-1i32, &i32::MIN), 1);
+        assert_eq!(Gcd.combine(&i32::MIN, &6i32), 2);
+        assert_eq!(Gcd.combine(&i64::MIN, &i64::MAX), 1);
+        assert_eq!(Gcd.combine(&i8::MIN, &-64i8), 64);
+        // `2^31` doesn't fit into i32, it is returned as `MIN` and keeps being `2^31` afterwards
+        assert_eq!(Gcd.combine(&i32::MIN, &0i32), i32::MIN);
+        assert_eq!(Gcd.combine(&Gcd.combine(&i32::MIN, &0i32), &12i32), 4);
+    }
+
+    #[test]
+    /// The only negative GCDs are `gcd(MIN, 0)` and `gcd(MIN, MIN)`, which are `MIN`
+    fn signed_gcd_min_test() {
+        assert_eq!(Gcd.combine(&i8::MIN, &0i8), i8::MIN);
+        assert_eq!(Gcd.combine(&0i8, &i8::MIN), i8::MIN);
+        assert_eq!(Gcd.combine(&i8::MIN, &i8::MIN), i8::MIN);
+        assert_eq!(Gcd.combine(&i64::MIN, &0i64), i64::MIN);
+        assert_eq!(Gcd.combine(&i64::MIN, &i64::MIN), i64::MIN);
+        for x in i8::MIN..=i8::MAX {
+            for y in i8::MIN..=i8::MAX {
+                let is_min: bool = (x == i8::MIN || x == 0) && (y == i8::MIN || y == 0);
+                if !is_min || (x == 0 && y == 0) {
+                    assert!(Gcd.combine(&x, &y) >= 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// GCD of all pairs of `[l..r]` indices in random 100-element array of signed numbers
+    fn random_100_i64s_gcd_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 100;
+        let v: Vec<i64> = (0..n)
+            .map(|_| rng.gen_range(-100..100) * rng.gen_range(1..4) * 6)
+            .collect();
+        let segtree: SegmentTree<i64, Gcd> = SegmentTree::new(&v, Gcd);
+
+        for l_idx in 0..n {
+            for r_idx in l_idx + 1..=n {
+                let gcd_correct: i64 = v[l_idx..r_idx].iter().fold(0, |g, &x| naive_gcd(g, x));
+                assert_eq!(segtree.get(l_idx, r_idx), gcd_correct);
+            }
+        }
+    }
+
+    #[test]
+    /// GCD of unsigned numbers and its identity
+    fn unsigned_gcd_test() {
+        assert_eq!(Monoid::<u64>::identity(&Gcd), 0);
+        assert_eq!(Gcd.combine(&0u64, &15u64), 15);
+        assert_eq!(Gcd.combine(&u64::MAX, &u64::MAX), u64::MAX);
+
+        let v: Vec<u32> = vec![12, 18, 24, 7, 14, 21];
+        let segtree: SegmentTree<u32, Gcd> = SegmentTree::new(&v, Gcd);
+        assert_eq!(segtree.get(0, 3), 6);
+        assert_eq!(segtree.get(3, 6), 7);
+        assert_eq!(segtree.get(0, 6), 1);
+    }
+
+    #[test]
+    /// Xor of all pairs of `[l..r]` indices in random 100-element array with point updates
+    fn random_100_u32s_xor_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 100;
+        let mut v: Vec<u32> = (0..n).map(|_| rng.gen()).collect();
+        let mut segtree: SegmentTree<u32, Xor> = SegmentTree::new(&v, Xor);
+        assert_eq!(Monoid::<u32>::identity(&Xor), 0);
+
+        for _ in 0..20 {
+            let idx: usize = rng.gen_range(0..n);
+            let new_val: u32 = rng.gen();
+            v[idx] = new_val;
+            segtree.set(idx, new_val);
+
+            for l_idx in 0..n {
+                for r_idx in l_idx + 1..=n {
+                    let xor_correct: u32 = v[l_idx..r_idx].iter().fold(0, |acc, &x| acc ^ x);
+                    assert_eq!(segtree.get(l_idx, r_idx), xor_correct);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Product of all pairs of `[l..r]` indices in random 30-element array of small numbers
+    fn random_30_i64s_product_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 30;
+        let v: Vec<i64> = (0..n).map(|_| rng.gen_range(-3..=3)).collect();
+        let segtree: SegmentTree<i64, Product> = SegmentTree::new(&v, Product);
+        assert_eq!(Monoid::<i64>::identity(&Product), 1);
+
+        for l_idx in 0..n {
+            for r_idx in l_idx + 1..=n {
+                let product_correct: i64 = v[l_idx..r_idx].iter().product();
+                assert_eq!(segtree.get(l_idx, r_idx), product_correct);
+            }
+        }
+    }
+
+    #[test]
+    /// Product of floating point numbers
+    fn f64_product_test() {
+        let v: Vec<f64> = vec![0.5, 4.0, -2.0, 0.25];
+        let segtree: SegmentTree<f64, Product> = SegmentTree::new(&v, Product);
+        assert_eq!(segtree.get(0, 2), 2.0);
+        assert_eq!(segtree.get(0, 4), -1.0);
+        assert_eq!(Monoid::<f64>::identity(&Product), 1.0);
+    }
+}
+
+#[cfg(test)]
+mod lazy_segment_tree_tests {
+    use super::*;
+    use monoid::{Min, Sum};
+    use rand::Rng;
+    use segtree::LazySegmentTree;
+
+    /// Composes two "add `x`" updates
+    fn compose_add(older: &i64, newer: &i64) -> i64 {
+        return older + newer;
+    }
+
+    /// Applies "add `x`" update to sum of `len` elements
+    fn apply_add_to_sum(value: &i64, x: &i64, len: usize) -> i64 {
+        return value + x * len as i64;
+    }
+
+    /// Applies "assign `x`" update to minimum of elements
+    fn apply_assign_to_min(_value: &i64, x: &i64, _len: usize) -> i64 {
+        return *x;
+    }
+
+    /// Newer assignment overrides older one
+    fn compose_assign(_older: &i64, newer: &i64) -> i64 {
+        return *newer;
+    }
+
+    #[test]
+    /// Add to segments of `[1..=10]` array and check sums
+    fn basic_add_sum_test() {
+        let v: Vec<i64> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let mut segtree: LazySegmentTree<i64, i64, Sum> =
+            LazySegmentTree::new(&v, Sum, apply_add_to_sum, compose_add);
+
+        segtree.update(0, 5, 10);
+        assert_eq!(segtree.get(0, 10), 105);
+        segtree.update(3, 8, -1);
+        assert_eq!(segtree.get(0, 10), 100);
+        assert_eq!(segtree.get(4, 5), 14);
+        assert_eq!(segtree.get(7, 8), 7);
+
+        segtree.set(4, 0);
+        assert_eq!(segtree.get(3, 6), 18);
+    }
+
+    #[test]
+    /// Random range additions, point assignments and sum queries on 200-element array
+    fn random_200_i64s_add_sum_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 200;
+        let mut v: Vec<i64> = (0..n).map(|_| rng.gen_range(-100..100)).collect();
+        let mut segtree: LazySegmentTree<i64, i64, Sum> =
+            LazySegmentTree::new(&v, Sum, apply_add_to_sum, compose_add);
+
+        for _ in 0..1000 {
+            let l_idx = rng.gen_range(0..n);
+            let r_idx = rng.gen_range(l_idx + 1..=n);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let x = rng.gen_range(-100..100);
+                    v[l_idx..r_idx].iter_mut().for_each(|el| *el += x);
+                    segtree.update(l_idx, r_idx, x);
+                }
+                1 => {
+                    let x = rng.gen_range(-100..100);
+                    v[l_idx] = x;
+                    segtree.set(l_idx, x);
+                }
+                _ => {
+                    let sum_correct: i64 = v[l_idx..r_idx].iter().sum();
+                    assert_eq!(segtree.get(l_idx, r_idx), sum_correct);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Update functions may be closures capturing their environment
+    fn capturing_closures_test() {
+        let v: Vec<i64> = vec![1, 2, 3, 4, 5];
+        let modulus: i64 = 7;
+        let mut segtree = LazySegmentTree::new(
+            &v,
+            Sum,
+            |value: &i64, x: &i64, len: usize| (value + x * len as i64) % modulus,
+            |older: &i64, newer: &i64| (older + newer) % modulus,
+        );
+
+        assert_eq!(segtree.get(0, 5), 15);
+        segtree.update(0, 5, 3);
+        assert_eq!(segtree.get(0, 5), 30 % modulus);
+        assert_eq!(segtree.get(1, 2), 5);
+        segtree.update(1, 3, 6);
+        assert_eq!(segtree.get(1, 2), 11 % modulus);
+        assert_eq!(segtree.get(2, 3), 12 % modulus);
+    }
+
+    #[test]
+    /// Random range assignments and minimum queries on 200-element array
+    fn random_200_i64s_assign_min_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 200;
+        let mut v: Vec<i64> = (0..n).map(|_| rng.gen_range(-100..100)).collect();
+        let mut segtree: LazySegmentTree<i64, i64, Min> =
+            LazySegmentTree::new(&v, Min, apply_assign_to_min, compose_assign);
+
+        for _ in 0..1000 {
+            let l_idx = rng.gen_range(0..n);
+            let r_idx = rng.gen_range(l_idx + 1..=n);
+            if rng.gen_bool(0.5) {
+                let x = rng.gen_range(-100..100);
+                v[l_idx..r_idx].iter_mut().for_each(|el| *el = x);
+                segtree.update(l_idx, r_idx, x);
+            } else {
+                let min_correct: i64 = *v[l_idx..r_idx].iter().min().unwrap();
+                assert_eq!(segtree.get(l_idx, r_idx), min_correct);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod bottom_up_segment_tree_tests {
+    use super::*;
+    use monoid::{FnMonoid, Sum};
+    use rand::Rng;
+    use segtree::BottomUpSegmentTree;
+
+    #[test]
+    /// Change every element in 101-element random array and check sum of all pairs of `[l..r]`
+    /// indices
+    fn random_101_i32s_get_set_add_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 101;
+        let mut v: Vec<i32> = (0..n).map(|_| rng.gen_range(-10..10)).collect();
+        let mut segtree: BottomUpSegmentTree<i32, Sum> = BottomUpSegmentTree::new(&v, Sum);
+
+        for idx_to_change in 0..n {
+            let new_val = rng.gen_range(-10..10);
+            v[idx_to_change] = new_val;
+            segtree.set(idx_to_change, new_val);
+
+            for l_idx in 0..n {
+                for r_idx in l_idx + 1..=n {
+                    let sum_correct: i32 = v[l_idx..r_idx].iter().sum();
+                    assert_eq!(segtree.get(l_idx, r_idx), sum_correct);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Non-commutative monoid (concatenation of strings) on arrays of sizes, which are not powers
+    /// of two
+    fn string_concatenation_test() {
+        let concat = FnMonoid::new(String::new(), |a: &String, b: &String| a.clone() + b);
+        for n in 1..=20 {
+            let v: Vec<String> = (0..n)
+                .map(|i| ((b'a' + i as u8) as char).to_string())
+                .collect();
+            let segtree = BottomUpSegmentTree::new(&v, concat.clone());
+
+            for l_idx in 0..n {
+                for r_idx in l_idx + 1..=n {
+                    assert_eq!(segtree.get(l_idx, r_idx), v[l_idx..r_idx].concat());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod persistent_segment_tree_tests {
+    use super::*;
+    use monoid::{Min, Sum};
+    use persistent_segtree::{PersistentSegmentTree, RangeKthSmallest};
+    use rand::Rng;
+
+    #[test]
+    /// Check that old versions don't change after `set`
+    fn basic_versions_test() {
+        let v: Vec<i32> = vec![1, 2, 3, 4, 5];
+        let mut segtree: PersistentSegmentTree<i32, Sum> = PersistentSegmentTree::new(&v, Sum);
+
+        let version_1 = segtree.set(0, 2, 10);
+        let version_2 = segtree.set(version_1, 0, -1);
+        // Branch from the initial version
+        let version_3 = segtree.set(0, 4, 0);
+
+        assert_eq!(segtree.versions(), 4);
+        assert_eq!(segtree.get(0, 0, 5), 15);
+        assert_eq!(segtree.get(version_1, 0, 5), 22);
+        assert_eq!(segtree.get(version_2, 0, 5), 20);
+        assert_eq!(segtree.get(version_2, 1, 3), 12);
+        assert_eq!(segtree.get(version_3, 0, 5), 10);
+    }
+
+    #[test]
+    /// Empty vector is accepted, it has a single version and no elements
+    fn empty_test() {
+        let segtree: PersistentSegmentTree<i32, Sum> = PersistentSegmentTree::new(&[], Sum);
+        assert_eq!(segtree.versions(), 1);
+        let _kth: RangeKthSmallest<i32> = RangeKthSmallest::new(&[]);
+    }
+
+    #[test]
+    #[should_panic]
+    /// Query of empty tree panics on bounds assert
+    fn empty_get_test() {
+        let segtree: PersistentSegmentTree<i32, Sum> = PersistentSegmentTree::new(&[], Sum);
+        segtree.get(0, 0, 1);
+    }
+
+    #[test]
+    /// Random `set` calls on random versions of 50-element array, every version is checked
+    fn random_50_i32s_versions_min_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 50;
+        let mut versions: Vec<Vec<i32>> = vec![(0..n).map(|_| rng.gen_range(-100..100)).collect()];
+        let mut segtree: PersistentSegmentTree<i32, Min> =
+            PersistentSegmentTree::new(&versions[0], Min);
+
+        for _ in 0..200 {
+            let version = rng.gen_range(0..versions.len());
+            let (i, new_val) = (rng.gen_range(0..n), rng.gen_range(-100..100));
+            let mut v = versions[version].clone();
+            v[i] = new_val;
+            versions.push(v);
+            assert_eq!(segtree.set(version, i, new_val), versions.len() - 1);
+        }
+
+        for (version, v) in versions.iter().enumerate() {
+            let l_idx = rng.gen_range(0..n);
+            let r_idx = rng.gen_range(l_idx + 1..=n);
+            let min_correct: i32 = *v[l_idx..r_idx].iter().min().unwrap();
+            assert_eq!(segtree.get(version, l_idx, r_idx), min_correct);
+        }
+    }
+
+    #[test]
+    /// Every k-th smallest element of every segment of random 60-element array with duplicates
+    fn random_60_i32s_kth_smallest_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 60;
+        let v: Vec<i32> = (0..n).map(|_| rng.gen_range(-20..20)).collect();
+        let kth = RangeKthSmallest::new(&v);
+
+        for l_idx in 0..n {
+            for r_idx in l_idx + 1..=n {
+                let mut sorted: Vec<i32> = v[l_idx..r_idx].to_vec();
+                sorted.sort();
+                for (k, el) in sorted.iter().enumerate() {
+                    assert_eq!(kth.kth_smallest(l_idx, r_idx, k), el);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod segment_tree_2d_tests {
+    use super::*;
+    use algebra::Matrix;
+    use monoid::{Max, Sum};
+    use rand::Rng;
+    use segtree_2d::SegmentTree2D;
+
+    /// Naive computation of sum on rectangle `r1..r2` x `c1..c2`
+    fn rectangle_sum(v: &[Vec<i32>], (r1, c1): (usize, usize), (r2, c2): (usize, usize)) -> i32 {
+        return v[r1..r2]
+            .iter()
+            .map(|row| row[c1..c2].iter().sum::<i32>())
+            .sum();
+    }
+
+    #[test]
+    /// Check sum of every rectangle in 3x4 matrix built from `Matrix`
+    fn basic_matrix_sum_test() {
+        let mut matrix: Matrix<i32> = Matrix::new(3, 4);
+        for r in 0..3 {
+            for c in 0..4 {
+                matrix.set(r, c, (4 * r + c) as i32);
+            }
+        }
+        let v: Vec<Vec<i32>> = (0..3).map(|r| matrix[r].to_vec()).collect();
+        let segtree: SegmentTree2D<i32, Sum> = SegmentTree2D::from_matrix(&matrix, Sum);
+
+        assert_eq!(segtree.shape(), (3, 4));
+        for r1 in 0..3 {
+            for r2 in r1 + 1..=3 {
+                for c1 in 0..4 {
+                    for c2 in c1 + 1..=4 {
+                        let sum_correct = rectangle_sum(&v, (r1, c1), (r2, c2));
+                        assert_eq!(segtree.get((r1, c1), (r2, c2)), sum_correct);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Random point updates and rectangle sum queries on random 13x7 matrix
+    fn random_13x7_i32s_get_set_sum_test() {
+        let mut rng = rand::thread_rng();
+        let (rows, cols) = (13, 7);
+        let mut v: Vec<Vec<i32>> = (0..rows)
+            .map(|_| (0..cols).map(|_| rng.gen_range(-100..100)).collect())
+            .collect();
+        let mut segtree: SegmentTree2D<i32, Sum> = SegmentTree2D::new(&v, Sum);
+
+        for _ in 0..1000 {
+            if rng.gen_bool(0.3) {
+                let (r, c, new_val) = (
+                    rng.gen_range(0..rows),
+                    rng.gen_range(0..cols),
+                    rng.gen_range(-100..100),
+                );
+                v[r][c] = new_val;
+                segtree.set(r, c, new_val);
+            } else {
+                let r1 = rng.gen_range(0..rows);
+                let r2 = rng.gen_range(r1 + 1..=rows);
+                let c1 = rng.gen_range(0..cols);
+                let c2 = rng.gen_range(c1 + 1..=cols);
+                let sum_correct = rectangle_sum(&v, (r1, c1), (r2, c2));
+                assert_eq!(segtree.get((r1, c1), (r2, c2)), sum_correct);
+            }
+        }
+    }
+
+    #[test]
+    /// Maximum on rectangles of random 10x10 matrix
+    fn random_10x10_i32s_max_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 10;
+        let v: Vec<Vec<i32>> = (0..n)
+            .map(|_| (0..n).map(|_| rng.gen_range(-1000..1000)).collect())
+            .collect();
+        let segtree: SegmentTree2D<i32, Max> = SegmentTree2D::new(&v, Max);
+
+        for _ in 0..500 {
+            let r1 = rng.gen_range(0..n);
+            let r2 = rng.gen_range(r1 + 1..=n);
+            let c1 = rng.gen_range(0..n);
+            let c2 = rng.gen_range(c1 + 1..=n);
+            let max_correct = v[r1..r2]
+                .iter()
+                .map(|row| *row[c1..c2].iter().max().unwrap())
+                .max()
+                .unwrap();
+            assert_eq!(segtree.get((r1, c1), (r2, c2)), max_correct);
+        }
+    }
+}
+
+#[cfg(test)]
+mod dynamic_segment_tree_tests {
+    use super::*;
+    use dynamic_segtree::{DynamicSegmentTree, LazyDynamicSegmentTree};
+    use monoid::{Max, Sum};
+    use rand::Rng;
+    use std::collections::BTreeMap;
+
+    #[test]
+    /// Point updates on timestamps spread over the whole `i64` range
+    fn basic_i64_keys_sum_test() {
+        let mut segtree: DynamicSegmentTree<i64, i64, Sum> =
+            DynamicSegmentTree::new(i64::MIN, i64::MAX, Sum);
+        segtree.set(i64::MIN, 5);
+        segtree.set(-1_000_000_000_000, 7);
+        segtree.set(0, 11);
+        segtree.set(i64::MAX - 1, 13);
+
+        assert_eq!(segtree.get(i64::MIN, i64::MAX), 36);
+        assert_eq!(segtree.get(i64::MIN + 1, 0), 7);
+        assert_eq!(segtree.get(0, 1), 11);
+        assert_eq!(segtree.get(1, i64::MAX), 13);
+        // Memory is proportional to touched keys, not to the range
+        assert!(segtree.nodes_count() <= 4 * 65);
+    }
+
+    #[test]
+    /// Random point assignments, range additions and sum queries on `u64` keys. Expected sum
+    /// is computed from history of operations
+    fn random_u64_keys_set_add_sum_test() {
+        let mut rng = rand::thread_rng();
+        let (lo, hi): (u64, u64) = (1 << 40, 1 << 60);
+        // Keys are taken from a small set, so segments often contain several of them
+        let keys: Vec<u64> = (0..50).map(|_| rng.gen_range(lo..hi)).collect();
+        // All additions and, for every assigned key, its value and number of additions before it
+        let mut adds: Vec<(u64, u64, i128)> = Vec::new();
+        let mut assigned: BTreeMap<u64, (i128, usize)> = BTreeMap::new();
+        let mut segtree: LazyDynamicSegmentTree<u64, i128, Sum> =
+            LazyDynamicSegmentTree::new(lo, hi, Sum);
+
+        for _ in 0..1000 {
+            let a = keys[rng.gen_range(0..keys.len())];
+            let b = keys[rng.gen_range(0..keys.len())];
+            let (l_key, r_key) = (a.min(b), a.max(b) + 1);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let new_val = rng.gen_range(-100..100);
+                    assigned.insert(a, (new_val, adds.len()));
+                    segtree.set(a, new_val);
+                }
+                1 => {
+                    let delta = rng.gen_range(-100..100);
+                    adds.push((l_key, r_key, delta));
+                    segtree.add(l_key, r_key, delta);
+                }
+                _ => {
+                    // Every addition contributes to every key of intersection ...
+                    let mut sum_correct: i128 = adds
+                        .iter()
+                        .map(|&(add_l, add_r, delta)| {
+                            let overlap = add_r.min(r_key).saturating_sub(add_l.max(l_key));
+                            delta * overlap as i128
+                        })
+                        .sum();
+                    // ... except additions, which were overwritten by assignment
+                    for (&key, &(value, adds_before)) in assigned.range(l_key..r_key) {
+                        sum_correct += value;
+                        for &(add_l, add_r, delta) in adds[..adds_before].iter() {
+                            if add_l <= key && key < add_r {
+                                sum_correct -= delta;
+                            }
+                        }
+                    }
+                    assert_eq!(segtree.get(l_key, r_key), sum_correct);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Maximum of sparse `i64` keys
+    fn random_i64_keys_max_test() {
+        let mut rng = rand::thread_rng();
+        let (lo, hi): (i64, i64) = (-1_000_000_000_000, 1_000_000_000_000);
+        let mut values: BTreeMap<i64, i32> = BTreeMap::new();
+        let mut segtree: DynamicSegmentTree<i64, i32, Max> = DynamicSegmentTree::new(lo, hi, Max);
+
+        for _ in 0..500 {
+            let (key, new_val) = (rng.gen_range(lo..hi), rng.gen_range(-1000..1000));
+            values.insert(key, new_val);
+            segtree.set(key, new_val);
+
+            let l_key = rng.gen_range(lo..hi);
+            let r_key = rng.gen_range(l_key + 1..=hi);
+            let max_correct = values.range(l_key..r_key).map(|(_, &v)| v).max();
+            assert_eq!(segtree.get(l_key, r_key), max_correct.unwrap_or(i32::MIN));
+        }
+    }
+
+    #[test]
+    /// Random point assignments, range additions and maximum queries. Pending additions must be
+    /// composed by addition, not by `combine` of `Max`
+    fn random_i64_keys_set_add_max_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 200;
+        // Unassigned keys are identity and stay so after additions
+        let mut v: Vec<Option<i64>> = vec![None; n];
+        let mut segtree: LazyDynamicSegmentTree<i64, i64, Max> =
+            LazyDynamicSegmentTree::new(0, n as i64, Max);
+
+        for _ in 0..1000 {
+            let l_idx = rng.gen_range(0..n);
+            let r_idx = rng.gen_range(l_idx + 1..=n);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let new_val = rng.gen_range(-100..100);
+                    v[l_idx] = Some(new_val);
+                    segtree.set(l_idx as i64, new_val);
+                }
+                1 => {
+                    let delta = rng.gen_range(-100..100);
+                    v[l_idx..r_idx]
+                        .iter_mut()
+                        .flatten()
+                        .for_each(|el| *el += delta);
+                    segtree.add(l_idx as i64, r_idx as i64, delta);
+                }
+                _ => {
+                    let max_correct: Option<i64> = v[l_idx..r_idx].iter().flatten().max().copied();
+                    assert_eq!(
+                        segtree.get(l_idx as i64, r_idx as i64),
+                        max_correct.unwrap_or(i64::MIN)
+                    );
+                }
+            }
+        }
+
+        // Two pending additions of different sign on the same node
+        let mut segtree: LazyDynamicSegmentTree<i64, i64, Max> =
+            LazyDynamicSegmentTree::new(0, 8, Max);
+        segtree.set(1, 10);
+        segtree.set(6, 20);
+        segtree.add(0, 8, 5);
+        segtree.add(0, 8, -3);
+        assert_eq!(segtree.get(0, 4), 12);
+        assert_eq!(segtree.get(4, 8), 22);
+        assert_eq!(segtree.get(0, 8), 22);
+    }
+
+    #[test]
+    /// Queries don't create nodes, also when pending additions are not pushed to children
+    fn get_does_not_allocate_test() {
+        let mut segtree: DynamicSegmentTree<i64, i64, Sum> =
+            DynamicSegmentTree::new(i64::MIN, i64::MAX, Sum);
+        segtree.set(42, 1);
+        let nodes_count = segtree.nodes_count();
+        assert_eq!(segtree.get(i64::MIN, 0), 0);
+        assert_eq!(segtree.get(0, 100), 1);
+        assert_eq!(segtree.nodes_count(), nodes_count);
+
+        let mut lazy: LazyDynamicSegmentTree<u64, u64, Sum> =
+            LazyDynamicSegmentTree::new(0, 1 << 40, Sum);
+        lazy.add(0, 1 << 40, 2);
+        lazy.add(1 << 20, 3 << 20, 1);
+        let nodes_count = lazy.nodes_count();
+        assert_eq!(lazy.get(5, 10), 10);
+        assert_eq!(lazy.get((1 << 20) - 1, (1 << 20) + 1), 5);
+        assert_eq!(lazy.get(0, 1 << 40), (2 << 40) + (2 << 20));
+        assert_eq!(lazy.nodes_count(), nodes_count);
+    }
+
+    #[test]
+    /// Addition to a segment, which is longer than `i32::MAX`, of narrow value type
+    fn narrow_value_type_wide_segment_test() {
+        let mut segtree: LazyDynamicSegmentTree<u64, i64, Sum> =
+            LazyDynamicSegmentTree::new(0, 1 << 40, Sum);
+        segtree.add(0, (1 << 32) + 1, 1);
+        assert_eq!(segtree.get(0, 1 << 40), (1 << 32) + 1);
+
+        let mut narrow: LazyDynamicSegmentTree<u64, i32, Sum> =
+            LazyDynamicSegmentTree::new(0, 1 << 40, Sum);
+        narrow.add(0, (1 << 32) + 1, 0);
+        assert_eq!(narrow.get(0, 1 << 40), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range addition overflows value type")]
+    /// Length of segment doesn't fit into `i32`, so it must not be truncated silently
+    fn narrow_value_type_overflow_test() {
+        let mut segtree: LazyDynamicSegmentTree<u64, i32, Sum> =
+            LazyDynamicSegmentTree::new(0, 1 << 40, Sum);
+        segtree.add(0, (1 << 32) + 1, 1);
+    }
+}
+
+#[cfg(test)]
+mod fenwick_tests {
+    use super::*;
+    use fenwick::{Fenwick, Fenwick2D, RangeFenwick};
+    use monoid::{Sum, Xor};
+    use rand::Rng;
+
+    #[test]
+    /// Random point additions and segment sums on 200-element array
+    fn random_200_i64s_add_get_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 200;
+        let mut v: Vec<i64> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
+        let mut fenwick: Fenwick<i64, Sum> = Fenwick::from_slice(&v, Sum);
+
+        for _ in 0..1000 {
+            let i = rng.gen_range(0..n);
+            let delta = rng.gen_range(-1000..1000);
+            v[i] += delta;
+            fenwick.add(i, &delta);
+
+            let l_idx = rng.gen_range(0..=n);
+            let r_idx = rng.gen_range(l_idx..=n);
+            assert_eq!(fenwick.prefix(r_idx), v[..r_idx].iter().sum::<i64>());
+            assert_eq!(
+                fenwick.get(l_idx, r_idx),
+                v[l_idx..r_idx].iter().sum::<i64>()
+            );
+        }
+    }
+
+    #[test]
+    /// Xor of segments, built with `new` and point additions
+    fn random_100_u32s_xor_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 100;
+        let v: Vec<u32> = (0..n).map(|_| rng.gen()).collect();
+        let mut fenwick: Fenwick<u32, Xor> = Fenwick::new(n, Xor);
+        for (i, el) in v.iter().enumerate() {
+            fenwick.add(i, el);
+        }
+
+        for l_idx in 0..n {
+            for r_idx in l_idx..=n {
+                let xor_correct = v[l_idx..r_idx].iter().fold(0, |acc, el| acc ^ el);
+                assert_eq!(fenwick.get(l_idx, r_idx), xor_correct);
+            }
+        }
+    }
+
+    #[test]
+    /// Search by cumulative weight on non-negative weights with zeros
+    fn random_100_i64s_lower_bound_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 100;
+        let v: Vec<i64> = (0..n).map(|_| rng.gen_range(0..5)).collect();
+        let fenwick: Fenwick<i64, Sum> = Fenwick::from_slice(&v, Sum);
+        let total: i64 = v.iter().sum();
+
+        for target in 0..=total + 1 {
+            let mut prefix_sum: i64 = 0;
+            let mut idx_correct = n;
+            for (i, el) in v.iter().enumerate() {
+                prefix_sum += el;
+                if prefix_sum >= target {
+                    idx_correct = i;
+                    break;
+                }
+            }
+            assert_eq!(fenwick.lower_bound(&target), idx_correct);
+        }
+    }
+
+    #[test]
+    /// Random range additions and segment sums on 150-element array
+    fn random_150_i64s_range_add_get_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 150;
+        let mut v: Vec<i64> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
+        let mut fenwick: RangeFenwick<i64, Sum> = RangeFenwick::from_slice(&v, Sum);
+
+        for _ in 0..1000 {
+            let l_idx = rng.gen_range(0..=n);
+            let r_idx = rng.gen_range(l_idx..=n);
+            if rng.gen_bool(0.5) {
+                let delta = rng.gen_range(-1000..1000);
+                v[l_idx..r_idx].iter_mut().for_each(|el| *el += delta);
+                fenwick.add(l_idx, r_idx, &delta);
+            } else {
+                assert_eq!(
+                    fenwick.get(l_idx, r_idx),
+                    v[l_idx..r_idx].iter().sum::<i64>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    /// Values larger than half of `i64::MAX` must not be doubled, when multiplied by 1
+    fn range_add_large_delta_test() {
+        let delta: i64 = i64::MAX / 2 + 1;
+        let mut fenwick: RangeFenwick<i64, Sum> = RangeFenwick::new(3, Sum);
+        fenwick.add(0, 1, &delta);
+        assert_eq!(fenwick.get(0, 1), delta);
+        assert_eq!(fenwick.get(0, 3), delta);
+        assert_eq!(fenwick.get(1, 3), 0);
+    }
+
+    #[test]
+    /// Random point additions and rectangle sums on 12x9 matrix
+    fn random_12x9_i64s_2d_test() {
+        let mut rng = rand::thread_rng();
+        let (rows, cols) = (12, 9);
+        let mut v: Vec<Vec<i64>> = vec![vec![0; cols]; rows];
+        let mut fenwick: Fenwick2D<i64, Sum> = Fenwick2D::new(rows, cols, Sum);
+
+        for _ in 0..1000 {
+            let (r, c, delta) = (
+                rng.gen_range(0..rows),
+                rng.gen_range(0..cols),
+                rng.gen_range(-100..100),
+            );
+            v[r][c] += delta;
+            fenwick.add(r, c, &delta);
+
+            let r1 = rng.gen_range(0..=rows);
+            let r2 = rng.gen_range(r1..=rows);
+            let c1 = rng.gen_range(0..=cols);
+            let c2 = rng.gen_range(c1..=cols);
+            let sum_correct: i64 = v[r1..r2]
+                .iter()
+                .map(|row| row[c1..c2].iter().sum::<i64>())
+                .sum();
+            assert_eq!(fenwick.get((r1, c1), (r2, c2)), sum_correct);
+        }
+    }
+}
+
+#[cfg(test)]
+mod sparse_table_tests {
+    use super::*;
+    use monoid::{FnMonoid, Gcd, Min, Monoid, Sum};
+    use rand::Rng;
+    use sparse_table::{DisjointSparseTable, SparseTable};
+
+    /// 2x2 matrix of i64s
+    type Matrix2 = [[i64; 2]; 2];
+
+    /// Multiplies two 2x2 matrices modulo `1_000_000_007`
+    fn multiply_matrices(a: &Matrix2, b: &Matrix2) -> Matrix2 {
+        let modulo: i64 = 1_000_000_007;
+        let mut result: Matrix2 = [[0; 2]; 2];
+        for (r, row) in result.iter_mut().enumerate() {
+            for (c, el) in row.iter_mut().enumerate() {
+                *el = (a[r][0] * b[0][c] + a[r][1] * b[1][c]) % modulo;
+            }
+        }
+        return result;
+    }
+
+    #[test]
+    /// Minimum and gcd of all pairs of `[l..r]` indices in random 300-element array
+    fn random_300_i32s_min_gcd_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 300;
+        let v: Vec<i32> = (0..n).map(|_| rng.gen_range(1..1000) * 6).collect();
+        let min_table: SparseTable<i32, Min> = SparseTable::new(&v, Min);
+        let gcd_table: SparseTable<i32, Gcd> = SparseTable::new(&v, Gcd);
+
+        for l_idx in 0..n {
+            let mut min_correct = i32::MAX;
+            let mut gcd_correct = 0;
+            for r_idx in l_idx + 1..=n {
+                min_correct = min_correct.min(v[r_idx - 1]);
+                gcd_correct = Gcd.combine(&gcd_correct, &v[r_idx - 1]);
+                assert_eq!(min_table.get(l_idx, r_idx), min_correct);
+                assert_eq!(gcd_table.get(l_idx, r_idx), gcd_correct);
+            }
+        }
+    }
+
+    #[test]
+    /// Sum of all pairs of `[l..r]` indices in random arrays of sizes, which are not powers of two
+    fn random_i64s_disjoint_sum_test() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 2, 3, 7, 100, 129] {
+            let v: Vec<i64> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
+            let table: DisjointSparseTable<i64, Sum> = DisjointSparseTable::new(&v, Sum);
+
+            for l_idx in 0..n {
+                for r_idx in l_idx + 1..=n {
+                    assert_eq!(table.get(l_idx, r_idx), v[l_idx..r_idx].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Non-commutative product of random 2x2 matrices
+    fn random_100_matrices_disjoint_product_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 100;
+        let v: Vec<Matrix2> = (0..n)
+            .map(|_| {
+                [
+                    [rng.gen_range(0..100), rng.gen_range(0..100)],
+                    [rng.gen_range(0..100), rng.gen_range(0..100)],
+                ]
+            })
+            .collect();
+        let product = FnMonoid::new([[1, 0], [0, 1]], multiply_matrices);
+        let table = DisjointSparseTable::new(&v, product);
+
+        for l_idx in 0..n {
+            let mut product_correct: Matrix2 = [[1, 0], [0, 1]];
+            for r_idx in l_idx + 1..=n {
+                product_correct = multiply_matrices(&product_correct, &v[r_idx - 1]);
+                assert_eq!(table.get(l_idx, r_idx), product_correct);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod dsu_tests {
+    use super::*;
+    use dsu::{Dsu, DsuWithData, RollbackDsu, RollbackDsuWithData};
+    use monoid::{Min, Sum};
+    use rand::Rng;
+
+    /// Naive components: merges labels of all elements on every union
+    fn naive_union(labels: &mut [usize], a: usize, b: usize) {
+        let (from, to) = (labels[b], labels[a]);
+        labels
+            .iter_mut()
+            .filter(|l| **l == from)
+            .for_each(|l| *l = to);
+    }
+
+    /// Number of distinct labels
+    fn naive_count(labels: &[usize]) -> usize {
+        let mut distinct: Vec<usize> = labels.to_vec();
+        distinct.sort();
+        distinct.dedup();
+        return distinct.len();
+    }
+
+    #[test]
+    /// Random unions on 100 elements, compared with naive labels
+    fn random_100_unions_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 100;
+        let mut labels: Vec<usize> = (0..n).collect();
+        let mut dsu = Dsu::new(n);
+
+        for _ in 0..150 {
+            let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            assert_eq!(dsu.union(a, b), labels[a] != labels[b]);
+            naive_union(&mut labels, a, b);
+
+            let (x, y) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            assert_eq!(dsu.same(x, y), labels[x] == labels[y]);
+            assert_eq!(
+                dsu.size(x),
+                labels.iter().filter(|&&l| l == labels[x]).count()
+            );
+            assert_eq!(dsu.count(), naive_count(&labels));
+        }
+    }
+
+    #[test]
+    /// Random unions with nested snapshots and rollbacks, compared with saved naive labels
+    fn random_rollback_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 50;
+        let mut labels: Vec<usize> = (0..n).collect();
+        let mut dsu = RollbackDsu::new(n);
+        let mut saved: Vec<(usize, Vec<usize>)> = Vec::new();
+
+        for _ in 0..500 {
+            match rng.gen_range(0..4) {
+                0 => saved.push((dsu.snapshot(), labels.clone())),
+                1 if !saved.is_empty() => {
+                    let (snapshot, old_labels) = saved.pop().unwrap();
+                    dsu.rollback(snapshot);
+                    labels = old_labels;
+                }
+                _ => {
+                    let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                    assert_eq!(dsu.union(a, b), labels[a] != labels[b]);
+                    naive_union(&mut labels, a, b);
+                }
+            }
+
+            for x in 0..n {
+                let y = rng.gen_range(0..n);
+                assert_eq!(dsu.same(x, y), labels[x] == labels[y]);
+            }
+            assert_eq!(dsu.count(), naive_count(&labels));
+        }
+    }
+
+    #[test]
+    /// Sum and minimum of weights of components
+    fn components_data_test() {
+        let weights: Vec<i64> = vec![5, -3, 8, 1, 0, 7];
+        let mut sums = DsuWithData::new(&weights, Sum);
+        let mut mins = DsuWithData::new(&weights, Min);
+
+        for (a, b) in [(0, 1), (2, 3), (1, 3), (4, 5)] {
+            sums.union(a, b);
+            mins.union(a, b);
+        }
+
+        assert_eq!(sums.count(), 2);
+        assert_eq!(*sums.data(2), 11);
+        assert_eq!(*sums.data(5), 7);
+        assert_eq!(*mins.data(0), -3);
+        assert_eq!(*mins.data(4), 0);
+        assert!(!sums.union(0, 2));
+        assert_eq!(sums.size(3), 4);
+    }
+
+    #[test]
+    /// Random unions with nested snapshots and rollbacks, sums of components are compared with
+    /// naive ones
+    fn random_rollback_data_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 50;
+        let weights: Vec<i64> = (0..n).map(|_| rng.gen_range(-100..100)).collect();
+        let mut labels: Vec<usize> = (0..n).collect();
+        let mut dsu = RollbackDsuWithData::new(&weights, Sum);
+        let mut saved: Vec<(usize, Vec<usize>)> = Vec::new();
+
+        for _ in 0..500 {
+            match rng.gen_range(0..4) {
+                0 => saved.push((dsu.snapshot(), labels.clone())),
+                1 if !saved.is_empty() => {
+                    let (snapshot, old_labels) = saved.pop().unwrap();
+                    dsu.rollback(snapshot);
+                    labels = old_labels;
+                }
+                _ => {
+                    let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                    assert_eq!(dsu.union(a, b), labels[a] != labels[b]);
+                    naive_union(&mut labels, a, b);
+                }
+            }
+
+            for x in 0..n {
+                let sum_correct: i64 = (0..n)
+                    .filter(|&y| labels[y] == labels[x])
+                    .map(|y| weights[y])
+                    .sum();
+                assert_eq!(*dsu.data(x), sum_correct);
+            }
+            assert_eq!(dsu.count(), naive_count(&labels));
+        }
+    }
+}
+
+#[cfg(test)]
+mod heap_tests {
+    use super::*;
+    use heap::IndexedHeap;
+    use rand::Rng;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn basic_heap_test() {
+        let mut heap: IndexedHeap<i32> = IndexedHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+
+        let handles: Vec<usize> = [5, 3, 8, 1, 9].iter().map(|&x| heap.push(x)).collect();
+        assert_eq!(handles, vec![0, 1, 2, 3, 4]);
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.peek_handle(), Some(3));
+
+        heap.decrease_key(4, 0);
+        assert_eq!(heap.peek(), Some(&0));
+        assert_eq!(heap.remove(0), 5);
+        assert!(!heap.contains(0));
+        assert_eq!(heap.get(0), None);
+        assert_eq!(heap.get(2), Some(&8));
+
+        let popped: Vec<i32> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![0, 1, 3, 8]);
+
+        // Max-heap with `Reverse`
+        let mut max_heap: IndexedHeap<Reverse<i32>> = IndexedHeap::new();
+        for x in [2, 7, 4] {
+            max_heap.push(Reverse(x));
+        }
+        assert_eq!(max_heap.pop(), Some(Reverse(7)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn decrease_key_greater_value_test() {
+        let mut heap: IndexedHeap<i32> = IndexedHeap::new();
+        let handle: usize = heap.push(5);
+        heap.decrease_key(handle, 6);
+    }
+
+    #[test]
+    fn random_operations_test() {
+        let mut rng = rand::thread_rng();
+        let mut heap: IndexedHeap<i32> = IndexedHeap::new();
+        // Naive model: `values[h]` is value of handle `h`, if it is in the heap
+        let mut values: Vec<Option<i32>> = Vec::new();
+
+        for _ in 0..5000 {
+            let alive: Vec<usize> = (0..values.len()).filter(|&h| values[h].is_some()).collect();
+            match rng.gen_range(0..4) {
+                0 => {
+                    let value: i32 = rng.gen_range(-1000..1000);
+                    assert_eq!(heap.push(value), values.len());
+                    values.push(Some(value));
+                }
+                1 => {
+                    let min: Option<i32> = alive.iter().map(|&h| values[h].unwrap()).min();
+                    // Equal values may be popped in any order, so popped handle is taken from heap
+                    let handle: Option<usize> = heap.peek_handle();
+                    assert_eq!(heap.pop(), min);
+                    if let Some(h) = handle {
+                        assert_eq!(values[h], min);
+                        values[h] = None;
+                    }
+                }
+                2 if !alive.is_empty() => {
+                    let h: usize = alive[rng.gen_range(0..alive.len())];
+                    let new_val: i32 = values[h].unwrap() - rng.gen_range(0..100);
+                    heap.decrease_key(h, new_val);
+                    values[h] = Some(new_val);
+                }
+                3 if !alive.is_empty() => {
+                    let h: usize = alive[rng.gen_range(0..alive.len())];
+                    assert_eq!(heap.remove(h), values[h].take().unwrap());
+                }
+                _ => {}
+            }
+            assert_eq!(heap.len(), values.iter().flatten().count());
+            assert_eq!(heap.peek(), values.iter().flatten().min());
+        }
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        let mut rng = rand::thread_rng();
+        let n: usize = 50;
+        let edges: Vec<(usize, usize, u64)> = (0..300)
+            .map(|_| {
+                (
+                    rng.gen_range(0..n),
+                    rng.gen_range(0..n),
+                    rng.gen_range(1..100),
+                )
+            })
+            .collect();
+
+        // Dijkstra's algorithm with decrease-key
+        let mut dist: Vec<u64> = vec![u64::MAX; n];
+        let mut handles: Vec<usize> = vec![0; n];
+        let mut heap: IndexedHeap<(u64, usize)> = IndexedHeap::new();
+        dist[0] = 0;
+        for v in 0..n {
+            handles[v] = heap.push((dist[v], v));
+        }
+        while let Some((d, v)) = heap.pop() {
+            if d == u64::MAX {
+                break;
+            }
+            for &(from, to, w) in &edges {
+                if from == v && heap.contains(handles[to]) && d + w < dist[to] {
+                    dist[to] = d + w;
+                    heap.decrease_key(handles[to], (dist[to], to));
+                }
+            }
+        }
+
+        // Bellman-Ford algorithm
+        let mut correct: Vec<u64> = vec![u64::MAX; n];
+        correct[0] = 0;
+        for _ in 0..n {
+            for &(from, to, w) in &edges {
+                if correct[from] != u64::MAX {
+                    correct[to] = correct[to].min(correct[from] + w);
+                }
+            }
+        }
+        assert_eq!(dist, correct);
+    }
+}
+
+#[cfg(test)]
+mod sort_stats_tests {
+    use super::*;
+    use rand::Rng;
+    use sort_stats::*;
+    use sortings::*;
+
+    /// Sorting function with observer
+    type ObservedSortFn = fn(&mut [Counted<i32>], &mut dyn FnMut(SortEvent));
+
+    /// Heapsort, which is written independently of `sortings`, returns number of swaps
+    fn reference_heap_sort_swaps(v: &mut [i32]) -> u64 {
+        let mut swaps: u64 = 0;
+        let mut sift_down = |v: &mut [i32], mut i: usize| loop {
+            let mut largest: usize = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < v.len() && v[child] > v[largest] {
+                    largest = child;
+                }
+            }
+            if largest == i {
+                return;
+            }
+            v.swap(i, largest);
+            swaps += 1;
+            i = largest;
+        };
+
+        for i in (0..v.len() / 2).rev() {
+            sift_down(v, i);
+        }
+        for end in (1..v.len()).rev() {
+            v.swap(0, end);
+            sift_down(&mut v[..end], 0);
+        }
+        return swaps + v.len().saturating_sub(1) as u64;
+    }
+
+    #[test]
+    fn collector_test() {
+        let v: Vec<i32> = vec![5, 4, 3, 2, 1];
+        let collector: StatsCollector = StatsCollector::new();
+        let mut wrapped: Vec<Counted<i32>> = collector.wrap(&v);
+        bubble_sort_with_observer(&mut wrapped, collector.observer());
+
+        assert_eq!(
+            collector.stats(),
+            SortStats {
+                comparisons: 10,
+                swaps: 10,
+                moves: 20,
+            }
+        );
+        let sorted: Vec<i32> = wrapped.into_iter().map(|x| x.into_inner()).collect();
+        assert_eq!(sorted, vec![1, 2, 3, 4, 5]);
+
+        collector.reset();
+        assert_eq!(collector.stats(), SortStats::default());
+
+        // Merge sort copies elements instead of swapping them
+        let mut wrapped: Vec<Counted<i32>> = collector.wrap(&v);
+        merge_sort(&mut wrapped);
+        let stats: SortStats = collector.stats();
+        assert_eq!(stats.swaps, 0);
+        assert!(stats.comparisons > 0 && stats.moves > 0);
+        assert_eq!(*wrapped[0].value(), 1);
+    }
+
+    #[test]
+    /// Swaps of O(NlogN) sorts are counted exactly: on `[3, 1, 2]` they are computed by hand, on
+    /// a fixed 200-element input heapsort is compared with reference implementation and every
+    /// sort is compared with replay of reported swaps
+    fn exact_swaps_test() {
+        // Every sort makes exactly two swaps on `[3, 1, 2]`
+        let small_sorts: [ObservedSortFn; 4] = [
+            |v, o| heap_sort_with_observer(v, o),
+            |v, o| quick_sort_with_observer(v, PivotStrategy::First, o),
+            |v, o| three_way_quick_sort_with_observer(v, PivotStrategy::First, o),
+            |v, o| intro_sort_with_observer(v, o),
+        ];
+        for sort in small_sorts {
+            let collector: StatsCollector = StatsCollector::new();
+            let mut wrapped: Vec<Counted<i32>> = collector.wrap(&[3, 1, 2]);
+            sort(&mut wrapped, &mut collector.observer());
+            assert_eq!(collector.stats().swaps, 2);
+        }
+
+        // Distinct elements, so a missed swap always changes the replay
+        let v: Vec<i32> = (0..200).map(|i| (i * 67) % 211).collect();
+        let collector: StatsCollector = StatsCollector::new();
+        let mut wrapped: Vec<Counted<i32>> = collector.wrap(&v);
+        heap_sort_with_observer(&mut wrapped, collector.observer());
+        assert_eq!(
+            collector.stats().swaps,
+            reference_heap_sort_swaps(&mut v.clone())
+        );
+
+        let sorts: [ObservedSortFn; 6] = [
+            |v, o| heap_sort_with_observer(v, o),
+            |v, o| quick_sort_with_observer(v, PivotStrategy::First, o),
+            |v, o| quick_sort_with_observer(v, PivotStrategy::MedianOfThree, o),
+            |v, o| quick_sort_with_observer(v, PivotStrategy::Ninther, o),
+            |v, o| three_way_quick_sort_with_observer(v, PivotStrategy::Ninther, o),
+            |v, o| intro_sort_with_observer(v, o),
+        ];
+        for sort in sorts {
+            let collector: StatsCollector = StatsCollector::new();
+            let mut wrapped: Vec<Counted<i32>> = collector.wrap(&v);
+            let mut count = collector.observer();
+            let mut replay: Vec<i32> = v.clone();
+            let mut swaps: u64 = 0;
+            sort(&mut wrapped, &mut |event| {
+                if let SortEvent::Swap(i, j) = event {
+                    replay.swap(i, j);
+                    swaps += 1;
+                }
+                count(event);
+            });
+
+            let sorted: Vec<i32> = wrapped.iter().map(|x| *x.value()).collect();
+            assert_eq!(replay, sorted);
+            assert!(sorted.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(collector.stats().swaps, swaps);
+            assert_eq!(collector.stats().moves, 2 * swaps);
+        }
+    }
+
+    #[test]
+    /// Swaps of insertion sort are equal to number of inversions
+    fn insertion_sort_swaps_test() {
+        let mut rng = rand::thread_rng();
+        let v: Vec<i32> = (0..200).map(|_| rng.gen_range(-100..100)).collect();
+        let inversions: u64 = count_inversions(&mut v.clone());
+
+        let collector: StatsCollector = StatsCollector::new();
+        let mut wrapped: Vec<Counted<i32>> = collector.wrap(&v);
+        insertion_sort_with_observer(&mut wrapped, collector.observer());
+        assert_eq!(collector.stats().swaps, inversions);
+    }
+
+    #[test]
+    fn compare_sorts_test() {
+        let mut rng = rand::thread_rng();
+        let v: Vec<i32> = (0..300).map(|_| rng.gen_range(-100..100)).collect();
+        let inversions: u64 = count_inversions(&mut v.clone());
+        let n: u64 = v.len() as u64;
+
+        let results: Vec<(&str, SortStats)> = compare_sorts(&v);
+        assert_eq!(results.len(), 17);
+        for (name, stats) in &results {
+            assert!(stats.comparisons > 0, "{name}");
+        }
+
+        let stats = |name: &str| results.iter().find(|(n, _)| *n == name).unwrap().1;
+        assert_eq!(stats("bubble_sort").comparisons, n * (n - 1) / 2);
+        assert_eq!(stats("bubble_sort").swaps, inversions);
+        assert_eq!(stats("insertion_sort").swaps, inversions);
+        assert_eq!(stats("gnome_sort").swaps, inversions);
+        assert!(stats("selection_sort").swaps < n);
+        assert_eq!(
+            stats("heap_sort").swaps,
+            reference_heap_sort_swaps(&mut v.clone())
+        );
+        assert_eq!(stats("merge_sort").swaps, 0);
+        // O(NlogN) sort makes fewer comparisons than O(N^2) one
+        assert!(stats("merge_sort").comparisons < stats("insertion_sort").comparisons);
+
+        // Swaps are equal to swaps of the same sort on unwrapped elements
+        let count_swaps = |sort: fn(&mut [i32], &mut dyn FnMut(SortEvent))| {
+            let mut swaps: u64 = 0;
+            sort(&mut v.clone(), &mut |event| {
+                if let SortEvent::Swap(i, j) = event {
+                    assert_ne!(i, j);
+                    swaps += 1;
+                }
+            });
+            return swaps;
+        };
+        assert_eq!(
+            stats("quick_sort (median of 3)").swaps,
+            count_swaps(|v, o| quick_sort_with_observer(v, PivotStrategy::MedianOfThree, o))
+        );
+        assert_eq!(
+            stats("intro_sort").swaps,
+            count_swaps(|v, o| intro_sort_with_observer(v, o))
+        );
+
+        let report: String = sort_report(&v);
+        assert_eq!(report.lines().count(), 18);
+        assert!(report.lines().next().unwrap().contains("comparisons"));
+        for (name, _) in &results {
+            assert!(report.contains(name));
+        }
+    }
 }
 
-#[cfg(test)]
-mod sorting_tests {
-    use super::*;
-    use rand::Rng;
-    use sortings::*;
+#[cfg(test)]
+mod sorting_tests {
+    use super::*;
+    use rand::rngs::ThreadRng;
+    use rand::Rng;
+    use sortings::*;
+    use std::cmp::Ordering;
+
+    /// Sorting function with comparator
+    type SortByFn = fn(&mut [i32], fn(&i32, &i32) -> Ordering);
+    /// Sorting function with key extraction function
+    type SortByKeyFn = fn(&mut [String], fn(&String) -> (usize, String));
+
+    /// Checks if v is sorted
+    fn is_sorted<T: Ord>(v: &[T]) -> bool {
+        let n = v.len();
+        for i in 1..n {
+            if v[i - 1] > v[i] {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    /// Basic 10 elements test, compares sorting_func result with correct result
+    ///
+    /// # Arguments:
+    /// * sorting_func - sorting function (accepts slice of i32s)
+    fn basic_10_elements_test(sorting_func: fn(&mut [i32])) {
+        let mut v: Vec<i32> = vec![6, 4, 7, 2, 3, 9, 1, 8, 10, 5];
+        let correct: Vec<i32> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+        (sorting_func)(&mut v);
+        assert_eq!(correct, v);
+    }
+
+    /// Randomly generated 1000 int32s test, checks if sorting_func result is sorted
+    ///
+    /// # Arguments:
+    /// * sorting_func - sorting function (accepts slice of i32s)
+    fn random_1000_i32s_test(sorting_func: fn(&mut [i32])) {
+        let mut rng = rand::thread_rng();
+        let n: usize = 1000;
+        // Generate n random elements in range -1000..1000
+        let mut v: Vec<i32> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
+
+        // Call sorting function with v
+        (sorting_func)(&mut v);
+        // Check if v is sorted
+        assert!(is_sorted(&v));
+    }
+
+    #[test]
+    /// Nested for loop iterating over testing functions and sorting functions
+    fn test_all_sorting_functions() {
+        let sorting_functions: [fn(&mut [i32]); 21] = [
+            bubble_sort,
+            selection_sort,
+            insertion_sort,
+            counting_sort,
+            merge_sort,
+            bottom_up_merge_sort,
+            |v| merge_sort_with_buffer(v, &mut Vec::new()),
+            |v| quick_sort(v, PivotStrategy::First),
+            |v| quick_sort(v, PivotStrategy::MedianOfThree),
+            |v| quick_sort(v, PivotStrategy::Random),
+            |v| quick_sort(v, PivotStrategy::Ninther),
+            |v| three_way_quick_sort(v, PivotStrategy::Random),
+            intro_sort,
+            radix_sort,
+            heap_sort,
+            |v| shell_sort(v, GapSequence::Ciura),
+            |v| shell_sort(v, GapSequence::Knuth),
+            comb_sort,
+            gnome_sort,
+            cocktail_shaker_sort,
+            odd_even_sort,
+        ];
+        let testing_functions = [basic_10_elements_test, random_1000_i32s_test];
+        for sorting_function in sorting_functions.iter() {
+            for testing_function in testing_functions.iter() {
+                (testing_function)(*sorting_function);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_and_single_element_test() {
+        let sorting_functions: [fn(&mut [i32]); 7] = [
+            bubble_sort,
+            selection_sort,
+            insertion_sort,
+            counting_sort,
+            merge_sort,
+            |v| quick_sort(v, PivotStrategy::First),
+            intro_sort,
+        ];
+        for sorting_function in sorting_functions {
+            let mut empty: Vec<i32> = Vec::new();
+            sorting_function(&mut empty);
+            assert!(empty.is_empty());
+
+            let mut single: Vec<i32> = vec![42];
+            sorting_function(&mut single);
+            assert_eq!(single, vec![42]);
+        }
+    }
+
+    #[test]
+    fn sort_by_comparator_test() {
+        let mut rng = rand::thread_rng();
+        let v: Vec<i32> = (0..300).map(|_| rng.gen_range(-100..100)).collect();
+        let mut descending: Vec<i32> = v.clone();
+        descending.sort_by(|a, b| b.cmp(a));
+
+        let sorting_functions: [SortByFn; 14] = [
+            bubble_sort_by,
+            selection_sort_by,
+            insertion_sort_by,
+            merge_sort_by,
+            bottom_up_merge_sort_by,
+            |v, cmp| quick_sort_by(v, PivotStrategy::Ninther, cmp),
+            |v, cmp| three_way_quick_sort_by(v, PivotStrategy::Random, cmp),
+            intro_sort_by,
+            heap_sort_by,
+            |v, cmp| shell_sort_by(v, GapSequence::Ciura, cmp),
+            comb_sort_by,
+            gnome_sort_by,
+            cocktail_shaker_sort_by,
+            odd_even_sort_by,
+        ];
+        for sorting_function in sorting_functions {
+            let mut u: Vec<i32> = v.clone();
+            sorting_function(&mut u, |a, b| b.cmp(a));
+            assert_eq!(u, descending);
+        }
+    }
+
+    #[test]
+    fn sort_by_key_test() {
+        let mut rng = rand::thread_rng();
+        // Non-Copy elements, sorted by length and then by content
+        let v: Vec<String> = (0..300)
+            .map(|_| "x".repeat(rng.gen_range(0..5)) + &rng.gen_range(0..10).to_string())
+            .collect();
+        let key = |s: &String| (s.len(), s.clone());
+        let mut correct: Vec<String> = v.clone();
+        correct.sort_by_key(key);
+
+        let sorting_functions: [SortByKeyFn; 14] = [
+            bubble_sort_by_key,
+            selection_sort_by_key,
+            insertion_sort_by_key,
+            merge_sort_by_key,
+            bottom_up_merge_sort_by_key,
+            |v, f| quick_sort_by_key(v, PivotStrategy::MedianOfThree, f),
+            |v, f| three_way_quick_sort_by_key(v, PivotStrategy::Ninther, f),
+            intro_sort_by_key,
+            heap_sort_by_key,
+            |v, f| shell_sort_by_key(v, GapSequence::Knuth, f),
+            comb_sort_by_key,
+            gnome_sort_by_key,
+            cocktail_shaker_sort_by_key,
+            odd_even_sort_by_key,
+        ];
+        for sorting_function in sorting_functions {
+            let mut u: Vec<String> = v.clone();
+            sorting_function(&mut u, key);
+            assert_eq!(u, correct);
+        }
+    }
+
+    #[test]
+    fn sort_sub_slice_test() {
+        let mut v: Vec<i32> = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
+        insertion_sort(&mut v[2..7]);
+        assert_eq!(v, vec![9, 8, 3, 4, 5, 6, 7, 2, 1]);
+        selection_sort(&mut v[..3]);
+        assert_eq!(v, vec![3, 8, 9, 4, 5, 6, 7, 2, 1]);
+        intro_sort(&mut v[7..]);
+        assert_eq!(v, vec![3, 8, 9, 4, 5, 6, 7, 1, 2]);
+    }
+
+    #[test]
+    fn observer_test() {
+        type ObservedSortFn = fn(&mut [i32], &mut dyn FnMut(SortEvent));
+        // Sorts, which swap only adjacent elements
+        let adjacent_swap_sorts: [ObservedSortFn; 5] = [
+            |v, observer| bubble_sort_with_observer(v, observer),
+            |v, observer| insertion_sort_with_observer(v, observer),
+            |v, observer| gnome_sort_with_observer(v, observer),
+            |v, observer| cocktail_shaker_sort_with_observer(v, observer),
+            |v, observer| odd_even_sort_with_observer(v, observer),
+        ];
+        let other_sorts: [ObservedSortFn; 10] = [
+            |v, observer| selection_sort_with_observer(v, observer),
+            |v, observer| shell_sort_with_observer(v, GapSequence::Ciura, observer),
+            |v, observer| shell_sort_with_observer(v, GapSequence::Knuth, observer),
+            |v, observer| comb_sort_with_observer(v, observer),
+            |v, observer| quick_sort_with_observer(v, PivotStrategy::First, observer),
+            |v, observer| quick_sort_with_observer(v, PivotStrategy::Random, observer),
+            |v, observer| quick_sort_with_observer(v, PivotStrategy::Ninther, observer),
+            |v, observer| three_way_quick_sort_with_observer(v, PivotStrategy::Ninther, observer),
+            |v, observer| heap_sort_with_observer(v, observer),
+            |v, observer| intro_sort_with_observer(v, observer),
+        ];
+
+        let mut rng = rand::thread_rng();
+        let v: Vec<i32> = (0..200).map(|_| rng.gen_range(-50..50)).collect();
+        let inversions: u64 = count_inversions(&mut v.clone());
+        for (i, sort) in adjacent_swap_sorts
+            .iter()
+            .chain(other_sorts.iter())
+            .enumerate()
+        {
+            let mut u: Vec<i32> = v.clone();
+            let mut events: Vec<SortEvent> = Vec::new();
+            sort(&mut u, &mut |event| events.push(event));
+            assert!(is_sorted(&u));
+
+            // Replaying swaps on initial slice gives the same result
+            let mut replay: Vec<i32> = v.clone();
+            let mut swaps: u64 = 0;
+            for event in events {
+                match event {
+                    SortEvent::Compare(a, b) => assert!(a < v.len() && b < v.len()),
+                    SortEvent::Swap(a, b) => {
+                        replay.swap(a, b);
+                        swaps += 1;
+                    }
+                }
+            }
+            assert_eq!(replay, u);
+
+            // Every adjacent swap fixes exactly one inversion
+            if i < adjacent_swap_sorts.len() {
+                assert_eq!(swaps, inversions);
+            }
+        }
+
+        // Sorted input needs no swaps, gaps are 4 and 1
+        let mut compares: usize = 0;
+        shell_sort_with_observer(&mut [1, 2, 3, 4, 5], GapSequence::Ciura, |event| {
+            assert!(matches!(event, SortEvent::Compare(_, _)));
+            compares += 1;
+        });
+        assert_eq!(compares, 5);
+    }
 
-    /// Checks if v is sorted
-    fn is_sorted<T: Copy + Ord>(v: &Vec<T>) -> bool {
-        let n = v.len();
-        for i in 1..n {
-            if v[i - 1] > v[i] {
-                return false;
+    #[test]
+    fn merge_sort_stability_test() {
+        let mut rng = rand::thread_rng();
+        // Pairs of (key, initial index), sorted only by key
+        let v: Vec<(i32, usize)> = (0..1000).map(|i| (rng.gen_range(0..10), i)).collect();
+
+        let mut top_down: Vec<(i32, usize)> = v.clone();
+        merge_sort_by(&mut top_down, |a, b| a.0.cmp(&b.0));
+        // Equal keys must keep increasing initial indices
+        assert!(top_down.windows(2).all(|w| w[0] < w[1]));
+
+        let mut correct: Vec<(i32, usize)> = v.clone();
+        correct.sort_by_key(|&(key, _)| key);
+        assert_eq!(top_down, correct);
+
+        // Reversed comparator sorts in descending order, still stable
+        let mut descending: Vec<(i32, usize)> = v.clone();
+        merge_sort_by(&mut descending, |a, b| b.0.cmp(&a.0));
+        correct.sort_by_key(|&(key, _)| -key);
+        assert_eq!(descending, correct);
+    }
+
+    #[test]
+    fn merge_sort_buffer_test() {
+        let mut rng = rand::thread_rng();
+        let mut buffer: Vec<String> = Vec::new();
+        for n in 0..50 {
+            let v: Vec<String> = (0..n).map(|_| rng.gen_range(0..100).to_string()).collect();
+            let mut correct: Vec<String> = v.clone();
+            correct.sort();
+
+            let mut top_down: Vec<String> = v.clone();
+            merge_sort_with_buffer(&mut top_down, &mut buffer);
+            assert_eq!(top_down, correct);
+
+            let mut bottom_up: Vec<String> = v.clone();
+            bottom_up_merge_sort_with_buffer(&mut bottom_up, &mut buffer);
+            assert_eq!(bottom_up, correct);
+        }
+    }
+
+    /// Inputs, which are known to be bad for naive quicksort: sorted, reversed, all equal, few
+    /// distinct values and "organ pipe"
+    fn adversarial_inputs(n: i32) -> Vec<Vec<i32>> {
+        return vec![
+            (0..n).collect(),
+            (0..n).rev().collect(),
+            vec![7; n as usize],
+            (0..n).map(|i| i % 3).collect(),
+            (0..n / 2).chain((0..n / 2).rev()).collect(),
+        ];
+    }
+
+    #[test]
+    fn quick_sort_adversarial_test() {
+        for v in adversarial_inputs(20_000) {
+            let mut correct: Vec<i32> = v.clone();
+            correct.sort();
+
+            for pivot in [
+                PivotStrategy::MedianOfThree,
+                PivotStrategy::Random,
+                PivotStrategy::Ninther,
+            ] {
+                let mut three_way: Vec<i32> = v.clone();
+                three_way_quick_sort(&mut three_way, pivot);
+                assert_eq!(three_way, correct);
             }
+
+            let mut random: Vec<i32> = v.clone();
+            quick_sort(&mut random, PivotStrategy::Random);
+            assert_eq!(random, correct);
+
+            let mut intro: Vec<i32> = v.clone();
+            intro_sort(&mut intro);
+            assert_eq!(intro, correct);
         }
-        return true;
     }
 
-    /// Basic 10 elements test, compares sorting_func result with correct result
-    ///
-    /// # Arguments:
-    /// * sorting_func - sorting function (accepts Vec of i32s)
-    fn basic_10_elements_test(sorting_func: fn(&mut Vec<i32>)) {
-        let mut v: Vec<i32> = vec![6, 4, 7, 2, 3, 9, 1, 8, 10, 5];
-        let correct: Vec<i32> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    #[test]
+    fn intro_sort_test() {
+        let mut rng = rand::thread_rng();
+        for n in 0..100 {
+            let mut v: Vec<String> = (0..n).map(|_| rng.gen_range(0..20).to_string()).collect();
+            let mut correct: Vec<String> = v.clone();
+            correct.sort();
 
-        (sorting_func)(&mut v);
-        assert_eq!(correct, v);
+            intro_sort(&mut v);
+            assert_eq!(v, correct);
+        }
     }
 
-    /// Randomly generated 1000 int32s test, checks if sorting_func result is sorted
-    ///
-    /// # Arguments:
-    /// * sorting_func - sorting function (accepts Vec of i32s)
-    fn random_1000_i32s_test(sorting_func: fn(&mut Vec<i32>)) {
+    /// Checks `radix_sort` and `counting_sort` against
+    /// `sort_unstable` on random values of `T`
+    fn integer_sorts_test<T: IntegerKey + std::fmt::Debug>(gen: impl Fn(&mut ThreadRng) -> T) {
         let mut rng = rand::thread_rng();
-        let n: usize = 1000;
-        // Generate n random elements in range -1000..1000
-        let mut v: Vec<i32> = (0..n).map(|_| rng.gen_range(-1000..1000)).collect();
+        let v: Vec<T> = (0..1000).map(|_| gen(&mut rng)).collect();
+        let mut correct: Vec<T> = v.clone();
+        correct.sort_unstable();
 
-        // Call sorting function with v
-        (sorting_func)(&mut v);
-        // Check if v is sorted
-        assert!(is_sorted(&v));
+        let mut radix: Vec<T> = v.clone();
+        radix_sort(&mut radix);
+        assert_eq!(radix, correct);
+
+        // Counting sort falls back to radix sort on a wide range of values
+        let mut counting: Vec<T> = v.clone();
+        counting_sort(&mut counting);
+        assert_eq!(counting, correct);
     }
 
     #[test]
-    /// Nested for loop iterating over testing functions and sorting functions
-    fn test_all_sorting_functions() {
-        let sorting_functions = [bubble_sort, selection_sort, insertion_sort, counting_sort];
-        let testing_functions = [basic_10_elements_test, random_1000_i32s_test];
-        for sorting_function in sorting_functions.iter() {
-            for testing_function in testing_functions.iter() {
-                (testing_function)(*sorting_function);
+    fn integer_sorts_all_types_test() {
+        integer_sorts_test(|rng| rng.gen::<u8>());
+        integer_sorts_test(|rng| rng.gen::<i8>());
+        integer_sorts_test(|rng| rng.gen_range(-300..300i16));
+        integer_sorts_test(|rng| rng.gen::<u32>() % 5000);
+        integer_sorts_test(|rng| rng.gen_range(-2000..2000i64));
+        integer_sorts_test(|rng| rng.gen::<u64>());
+        integer_sorts_test(|rng| rng.gen::<i128>());
+        integer_sorts_test(|rng| rng.gen::<usize>());
+        integer_sorts_test(|rng| rng.gen::<isize>());
+
+        let mut v: Vec<i128> = vec![i128::MAX, 0, -1, i128::MIN, 1, i128::MIN + 1];
+        radix_sort(&mut v);
+        assert_eq!(v, vec![i128::MIN, i128::MIN + 1, -1, 0, 1, i128::MAX]);
+
+        let mut v: Vec<u64> = vec![u64::MAX, 0, 1 << 40, 3];
+        radix_sort(&mut v);
+        assert_eq!(v, vec![0, 3, 1 << 40, u64::MAX]);
+    }
+
+    #[test]
+    /// Sparse keys spanning the whole `i32` range must not allocate `2^32` counters
+    fn counting_sort_sparse_wide_range_test() {
+        let mut v: Vec<i32> = vec![i32::MAX, 0, i32::MIN, -5, 7, i32::MIN, 3];
+        counting_sort(&mut v);
+        assert_eq!(v, vec![i32::MIN, i32::MIN, -5, 0, 3, 7, i32::MAX]);
+
+        let mut items: Vec<(i64, usize)> = (0..100usize)
+            .map(|i| ((i as i64 % 3 - 1) << 50, i))
+            .collect();
+        let mut correct: Vec<(i64, usize)> = items.clone();
+        correct.sort_by_key(|item| item.0);
+        counting_sort_by_key(&mut items, |item| item.0);
+        assert_eq!(items, correct);
+    }
+
+    #[test]
+    fn integer_sorts_by_key_test() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Item {
+            key: i32,
+            name: String,
+        }
+
+        let mut rng = rand::thread_rng();
+        let v: Vec<Item> = (0..1000)
+            .map(|i| Item {
+                key: rng.gen_range(-20..20),
+                name: i.to_string(),
+            })
+            .collect();
+        let mut correct: Vec<Item> = v.clone();
+        correct.sort_by_key(|item| item.key);
+
+        let mut counting: Vec<Item> = v.clone();
+        counting_sort_by_key(&mut counting, |item| item.key);
+        assert_eq!(counting, correct);
+
+        let mut radix: Vec<Item> = v.clone();
+        radix_sort_by_key(&mut radix, |item| item.key);
+        assert_eq!(radix, correct);
+
+        // Sort by a wider key in descending order
+        let mut descending: Vec<Item> = v.clone();
+        radix_sort_by_key(&mut descending, |item| -(item.key as i64) << 40);
+        correct.sort_by_key(|item| -item.key);
+        assert_eq!(descending, correct);
+    }
+
+    /// Checks, that `v[k]` is `correct[k]` and `v` is partitioned around it
+    fn is_partitioned_at(v: &[i32], k: usize, correct: &[i32]) -> bool {
+        return v[k] == correct[k]
+            && v[..k].iter().all(|x| *x <= v[k])
+            && v[k + 1..].iter().all(|x| *x >= v[k]);
+    }
+
+    #[test]
+    fn selection_test() {
+        let mut rng = rand::thread_rng();
+        let selection_functions: [fn(&mut [i32], usize); 5] = [
+            nth_element,
+            median_of_medians_select,
+            |v, k| quick_select(v, k, PivotStrategy::First),
+            |v, k| quick_select(v, k, PivotStrategy::Random),
+            |v, k| nth_element_by_key(v, k, |&x| x),
+        ];
+        for n in [1, 2, 5, 17, 100, 1000] {
+            let v: Vec<i32> = (0..n).map(|_| rng.gen_range(-50..50)).collect();
+            let mut correct: Vec<i32> = v.clone();
+            correct.sort();
+
+            for selection_function in selection_functions {
+                for k in [0, n / 3, n / 2, n - 1] {
+                    let mut u: Vec<i32> = v.clone();
+                    selection_function(&mut u, k);
+                    assert!(is_partitioned_at(&u, k, &correct));
+                }
+            }
+        }
+
+        // Largest element with reversed comparator
+        let mut v: Vec<i32> = vec![3, 9, 1, 7];
+        nth_element_by(&mut v, 0, |a, b| b.cmp(a));
+        assert_eq!(v[0], 9);
+    }
+
+    #[test]
+    fn selection_adversarial_test() {
+        for v in adversarial_inputs(20_000) {
+            let mut correct: Vec<i32> = v.clone();
+            correct.sort();
+            let k: usize = v.len() / 2;
+
+            let mut u: Vec<i32> = v.clone();
+            nth_element(&mut u, k);
+            assert!(is_partitioned_at(&u, k, &correct));
+
+            let mut u: Vec<i32> = v.clone();
+            median_of_medians_select(&mut u, k);
+            assert!(is_partitioned_at(&u, k, &correct));
+        }
+    }
+
+    #[test]
+    fn partial_sort_test() {
+        let mut rng = rand::thread_rng();
+        let v: Vec<i32> = (0..500).map(|_| rng.gen_range(-100..100)).collect();
+        let mut correct: Vec<i32> = v.clone();
+        correct.sort();
+
+        for k in [0, 1, 10, 499, 500, 1000] {
+            let mut u: Vec<i32> = v.clone();
+            partial_sort(&mut u, k);
+            let k: usize = k.min(v.len());
+            assert_eq!(u[..k], correct[..k]);
+
+            // Remaining elements are still there
+            u.sort();
+            assert_eq!(u, correct);
+        }
+
+        let mut descending: Vec<i32> = v.clone();
+        partial_sort_by_key(&mut descending, 5, |&x| -x);
+        assert_eq!(
+            descending[..5],
+            correct[495..].iter().rev().copied().collect::<Vec<i32>>()
+        );
+    }
+
+    #[test]
+    fn top_k_test() {
+        let mut rng = rand::thread_rng();
+        let v: Vec<i32> = (0..1000).map(|_| rng.gen_range(-1000..1000)).collect();
+        let mut correct: Vec<i32> = v.clone();
+        correct.sort();
+
+        for k in [0, 1, 7, 1000, 2000] {
+            let top: Vec<i32> = top_k(v.iter().copied(), k);
+            assert_eq!(top, correct[..k.min(v.len())]);
+        }
+
+        // Streaming: iterator is never collected
+        let top: Vec<u64> = top_k_by(1..=100_000u64, 3, |a, b| b.cmp(a));
+        assert_eq!(top, vec![100_000, 99_999, 99_998]);
+
+        let words: Vec<&str> = vec!["ccc", "a", "dddd", "bb"];
+        assert_eq!(top_k_by_key(words, 2, |s| s.len()), vec!["a", "bb"]);
+    }
+
+    #[test]
+    fn parallel_sorts_test() {
+        let mut rng = rand::thread_rng();
+        let v: Vec<i64> = (0..20_000).map(|_| rng.gen_range(-1000..1000)).collect();
+        let mut correct: Vec<i64> = v.clone();
+        merge_sort(&mut correct);
+
+        for threads in [1, 2, 3, 8] {
+            for sequential_cutoff in [0, 100, 1 << 20] {
+                let config = ParallelConfig {
+                    threads,
+                    sequential_cutoff,
+                };
+
+                let mut merge: Vec<i64> = v.clone();
+                parallel_merge_sort(&mut merge, config);
+                assert_eq!(merge, correct);
+
+                let mut sample: Vec<i64> = v.clone();
+                parallel_sample_sort(&mut sample, config);
+                assert_eq!(sample, correct);
+            }
+        }
+
+        for v in adversarial_inputs(20_000) {
+            let mut correct: Vec<i32> = v.clone();
+            intro_sort(&mut correct);
+
+            let mut sample: Vec<i32> = v.clone();
+            parallel_sample_sort(&mut sample, ParallelConfig::default());
+            assert_eq!(sample, correct);
+        }
+
+        let mut empty: Vec<i32> = Vec::new();
+        parallel_merge_sort(&mut empty, ParallelConfig::default());
+        parallel_sample_sort(&mut empty, ParallelConfig::default());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn parallel_sorts_by_test() {
+        let mut rng = rand::thread_rng();
+        // Pairs of (key, initial index), sorted only by key
+        let v: Vec<(i32, usize)> = (0..20_000).map(|i| (rng.gen_range(0..100), i)).collect();
+        let config = ParallelConfig {
+            threads: 4,
+            sequential_cutoff: 1000,
+        };
+
+        // Parallel merge sort is stable, so it matches sequential merge sort exactly
+        let mut correct: Vec<(i32, usize)> = v.clone();
+        merge_sort_by_key(&mut correct, |&(key, _)| -key);
+        let mut merge: Vec<(i32, usize)> = v.clone();
+        parallel_merge_sort_by_key(&mut merge, config, |&(key, _)| -key);
+        assert_eq!(merge, correct);
+
+        let mut sample: Vec<(i32, usize)> = v.clone();
+        parallel_sample_sort_by(&mut sample, config, |a, b| b.0.cmp(&a.0));
+        assert!(sample.windows(2).all(|w| w[0].0 >= w[1].0));
+        sample.sort();
+        let mut sorted: Vec<(i32, usize)> = v.clone();
+        sorted.sort();
+        assert_eq!(sample, sorted);
+    }
+
+    #[test]
+    fn count_inversions_test() {
+        let mut v: Vec<i32> = vec![3, 1, 2, 2, 5, 0];
+        assert_eq!(count_inversions(&mut v), 8);
+        assert_eq!(v, vec![0, 1, 2, 2, 3, 5]);
+        assert_eq!(count_inversions(&mut v), 0);
+
+        let mut rng = rand::thread_rng();
+        let mut v: Vec<i32> = (0..500).map(|_| rng.gen_range(-50..50)).collect();
+        let mut naive: u64 = 0;
+        for i in 0..v.len() {
+            for j in i + 1..v.len() {
+                if v[i] > v[j] {
+                    naive += 1;
+                }
             }
         }
+        assert_eq!(count_inversions(&mut v), naive);
+        assert!(is_sorted(&v));
     }
 }
 
@@ -195,7 +2234,7 @@ mod gcd_tests {
     /// Compute GCD of two common iteratively (loop over all numbers 1..a)
     fn iterative_gcd(a: i32, b: i32) -> i32 {
         let mut res = 1;
-        for i in 1..a {
+        for i in 1..=a {
             if a % i == 0 && b % i == 0 {
                 res = i;
             }
@@ -262,3 +2301,475 @@ mod gcd_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod matrix_tests {
+    use super::*;
+    use algebra::{linear_recurrence, linear_recurrence_mod, Matrix, ShapeMismatch};
+    use rand::Rng;
+    use std::iter::Sum;
+    use std::ops::Mul;
+
+    /// Naive product of two matrices, reference for tested multiplications
+    fn multiply<T>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T>
+    where
+        T: Copy + Default + Mul<Output = T> + Sum,
+    {
+        let ((n, k), (_, m)) = (a.shape(), b.shape());
+        let mut result = Matrix::new(n, m);
+        for r in 0..n {
+            for c in 0..m {
+                let val: T = (0..k).map(|i| a[r][i] * b[i][c]).sum();
+                result.set(r, c, val);
+            }
+        }
+        return result;
+    }
+
+    /// Random `n` x `m` integer matrix with values in `-range..=range`
+    fn random_matrix(n: usize, m: usize, range: i64) -> Matrix<i64> {
+        let mut rng = rand::thread_rng();
+        return Matrix::from_fn(n, m, |_, _| rng.gen_range(-range..=range));
+    }
+
+    /// Converts integer matrix to floating point one
+    fn to_float(a: &Matrix<i64>) -> Matrix<f64> {
+        let (n, m) = a.shape();
+        return Matrix::from_fn(n, m, |r, c| a[r][c] as f64);
+    }
+
+    #[test]
+    /// Precomputed determinants, ranks and solutions
+    fn basic_elimination_test() {
+        let a: Matrix<i64> =
+            Matrix::from_vec(vec![vec![2, -3, 1], vec![2, 0, -1], vec![1, 4, 5]]).unwrap();
+        assert_eq!(a.determinant(), 49);
+        assert_eq!(to_float(&a).determinant().round(), 49.0);
+        assert_eq!(a.rank(), 3);
+        assert_eq!(a.solve(&[-3, 2, 24]), Some(vec![2, 3, 2]));
+
+        let singular: Matrix<i64> =
+            Matrix::from_vec(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]).unwrap();
+        assert_eq!(singular.determinant(), 0);
+        assert_eq!(singular.rank(), 2);
+        assert!(singular.inverse().is_none());
+        assert!(singular.solve(&[1, 2, 3]).is_none());
+        assert!(to_float(&singular).inverse().is_none());
+        assert!(to_float(&singular).solve(&[1.0, 3.0, 0.0]).is_none());
+
+        // Consistent system with a free variable
+        let x = to_float(&singular).solve(&[6.0, 12.0, 2.0]).unwrap();
+        assert!((x[0] + 2.0 * x[1] + 3.0 * x[2] - 6.0).abs() < 1e-9);
+        assert!((x[0] + x[2] - 2.0).abs() < 1e-9);
+
+        // Solution is not integer
+        assert!(Matrix::<i64>::from_vec(vec![vec![2]])
+            .unwrap()
+            .solve(&[1])
+            .is_none());
+        assert_eq!(
+            Matrix::<f64>::from_vec(vec![vec![2.0]])
+                .unwrap()
+                .solve(&[1.0]),
+            Some(vec![0.5])
+        );
+
+        let rectangular: Matrix<i64> =
+            Matrix::from_vec(vec![vec![1, 2, 3, 4], vec![2, 4, 6, 8]]).unwrap();
+        assert_eq!(rectangular.rank(), 1);
+        assert!(rectangular.inverse().is_none());
+    }
+
+    #[test]
+    /// Elimination of matrices with large values stays exact, while minors fit into `i128`
+    fn large_values_elimination_test() {
+        let big: i64 = 1_000_000_000;
+        let a: Matrix<i64> = Matrix::from_vec(vec![
+            vec![big, big - 1, 3],
+            vec![2, big + 7, -big],
+            vec![big + 2, 2 * big + 6, 3 - big],
+        ])
+        .unwrap();
+        assert_eq!(a.rank(), 2);
+        assert_eq!(a.determinant(), 0);
+
+        let b: Matrix<i64> = Matrix::from_vec(vec![vec![big, 1], vec![1, big]]).unwrap();
+        assert_eq!(b.determinant(), big * big - 1);
+        assert_eq!(b.solve(&[big + 1, big + 1]), Some(vec![1, 1]));
+    }
+
+    #[test]
+    /// Empty matrix is invertible and its system has the empty solution
+    fn empty_matrix_elimination_test() {
+        let a: Matrix<i64> = Matrix::new(0, 0);
+        assert_eq!(a.determinant(), 1);
+        assert_eq!(a.rank(), 0);
+        assert_eq!(a.inverse(), Some(Matrix::new(0, 0)));
+        assert_eq!(a.solve(&[]), Some(vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "elimination overflows i128")]
+    /// Product of two minors of 3x3 matrix with values around `10^12` doesn't fit into `i128`
+    fn elimination_overflow_test() {
+        let big: i64 = 1_000_000_000_000;
+        let a: Matrix<i64> = Matrix::from_vec(vec![
+            vec![big, 2 * big + 1, 3 * big + 5],
+            vec![4 * big + 7, 5 * big, 6 * big + 1],
+            vec![7 * big + 3, 8 * big + 9, 9 * big],
+        ])
+        .unwrap();
+        a.rank();
+    }
+
+    #[test]
+    /// Integer and floating point determinants and ranks of random matrices are equal
+    fn random_determinant_and_rank_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let (n, m) = (rng.gen_range(1..=6), rng.gen_range(1..=6));
+            let a = random_matrix(n, m, 3);
+            assert_eq!(a.rank(), to_float(&a).rank());
+            if n == m {
+                let det: f64 = to_float(&a).determinant();
+                assert!((a.determinant() as f64 - det).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    /// Product of random matrix and its inverse is identity matrix, solutions satisfy equations
+    fn random_inverse_and_solve_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let n: usize = rng.gen_range(1..=6);
+            let a = random_matrix(n, n, 10);
+            let x: Vec<i64> = (0..n).map(|_| rng.gen_range(-10..=10)).collect();
+            let b: Vec<i64> = (0..n)
+                .map(|r| (0..n).map(|c| a[r][c] * x[c]).sum())
+                .collect();
+            if a.determinant() == 0 {
+                assert!(a.inverse().is_none());
+                continue;
+            }
+            assert_eq!(a.solve(&b), Some(x.clone()));
+
+            let float_a = to_float(&a);
+            let float_b: Vec<f64> = b.iter().map(|&v| v as f64).collect();
+            let float_x: Vec<f64> = float_a.solve(&float_b).unwrap();
+            for (u, &v) in float_x.iter().zip(x.iter()) {
+                assert!((u - v as f64).abs() < 1e-6);
+            }
+
+            let product = multiply(&float_a, &float_a.inverse().unwrap());
+            for r in 0..n {
+                for c in 0..n {
+                    let expected: f64 = if r == c { 1.0 } else { 0.0 };
+                    assert!((product[r][c] - expected).abs() < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Inverse of unimodular integer matrix (product of unit lower and upper triangular ones)
+    fn integer_inverse_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let n: usize = rng.gen_range(1..=6);
+            let (mut lower, mut upper) = (random_matrix(n, n, 3), random_matrix(n, n, 3));
+            for r in 0..n {
+                for c in 0..n {
+                    if r == c {
+                        lower.set(r, c, 1);
+                        upper.set(r, c, 1);
+                    } else if r < c {
+                        lower.set(r, c, 0);
+                    } else {
+                        upper.set(r, c, 0);
+                    }
+                }
+            }
+            let a: Matrix<i64> = (lower * upper).unwrap();
+
+            assert_eq!(a.determinant(), 1);
+            let inverse: Matrix<i64> = a.inverse().unwrap();
+            assert_eq!((a * inverse).unwrap(), Matrix::identity(n));
+        }
+    }
+
+    #[test]
+    /// Constructors, iterators, transposition, submatrices, concatenation and printing
+    fn structural_operations_test() {
+        assert!(Matrix::from_vec(vec![vec![1, 2], vec![3]]).is_none());
+        let a: Matrix<i32> = Matrix::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(a, Matrix::from_fn(2, 3, |r, c| (3 * r + c + 1) as i32));
+        assert_eq!(a.shape(), (2, 3));
+
+        let rows: Vec<&[i32]> = a.iter_rows().collect();
+        assert_eq!(rows, vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+        assert_eq!(a.iter_column(1).copied().collect::<Vec<i32>>(), vec![2, 5]);
+        let sums: Vec<i32> = a.iter_columns().map(|column| column.sum()).collect();
+        assert_eq!(sums, vec![5, 7, 9]);
+        assert_eq!(
+            a.iter().copied().collect::<Vec<i32>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+
+        let t: Matrix<i32> = a.transpose();
+        assert_eq!(t.shape(), (3, 2));
+        assert_eq!(
+            t,
+            Matrix::from_vec(vec![vec![1, 4], vec![2, 5], vec![3, 6]]).unwrap()
+        );
+        assert_eq!(t.transpose(), a);
+
+        let identity: Matrix<i32> = Matrix::identity(2);
+        assert_eq!(
+            identity,
+            Matrix::from_vec(vec![vec![1, 0], vec![0, 1]]).unwrap()
+        );
+
+        assert_eq!(
+            a.submatrix(0..2, 1..3),
+            Matrix::from_vec(vec![vec![2, 3], vec![5, 6]])
+        );
+        assert_eq!(a.submatrix(1..1, 0..3).unwrap().shape(), (0, 3));
+        assert!(a.submatrix(0..3, 0..1).is_none());
+
+        let h: Matrix<i32> = a.concat_horizontal(&identity).unwrap();
+        assert_eq!(h[1], vec![4, 5, 6, 0, 1]);
+        assert!(a.concat_horizontal(&t).is_err());
+        let v: Matrix<i32> = a.concat_vertical(&a).unwrap();
+        assert_eq!(v.shape(), (4, 3));
+        assert_eq!(v.submatrix(2..4, 0..3).unwrap(), a);
+        assert!(a.concat_vertical(&t).is_err());
+
+        let b: Matrix<i32> = Matrix::from_vec(vec![vec![1, -20], vec![300, 4]]).unwrap();
+        assert_eq!(b.to_string(), "  1 -20\n300   4");
+        let f: Matrix<f64> = Matrix::from_vec(vec![vec![0.5, 1.0]]).unwrap();
+        assert_eq!(format!("{:.2}", f), "0.50 1.00");
+        assert_eq!(format!("{:5}", f), "  0.5     1");
+    }
+
+    #[test]
+    /// Products of random matrices of different shapes, including ones, which are not multiples
+    /// of tile size
+    fn random_multiplication_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let (n, k, m) = (
+                rng.gen_range(1..=70),
+                rng.gen_range(1..=70),
+                rng.gen_range(1..=70),
+            );
+            let (a, b) = (random_matrix(n, k, 100), random_matrix(k, m, 100));
+            let product: Matrix<i64> = (&a * &b).unwrap();
+            assert_eq!(product.shape(), (n, m));
+            assert_eq!(product, multiply(&a, &b));
+            assert!((&b * &a).is_err() || n == m);
+        }
+
+        // Non-square product of different matrices
+        let a: Matrix<i32> = Matrix::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        let b: Matrix<i32> = Matrix::from_vec(vec![vec![7, 8], vec![9, 10], vec![11, 12]]).unwrap();
+        assert_eq!(
+            (a * b).ok(),
+            Matrix::from_vec(vec![vec![58, 64], vec![139, 154]])
+        );
+    }
+
+    #[test]
+    /// Strassen's multiplication of random square matrices, even and odd sizes larger than
+    /// threshold are padded differently
+    fn strassen_test() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 5, 127, 128, 129, 200, 257] {
+            let (a, b) = (random_matrix(n, n, 1000), random_matrix(n, n, 1000));
+            assert_eq!(a.strassen_mul(&b).unwrap(), multiply(&a, &b));
+        }
+        assert!(random_matrix(2, 3, 1)
+            .strassen_mul(&random_matrix(3, 2, 1))
+            .is_err());
+        assert!(random_matrix(2, 2, 1)
+            .strassen_mul(&random_matrix(3, 3, 1))
+            .is_err());
+
+        let n: usize = rng.gen_range(130..=160);
+        let a: Matrix<f64> = Matrix::from_fn(n, n, |_, _| rng.gen_range(-1.0..1.0));
+        let b: Matrix<f64> = Matrix::from_fn(n, n, |_, _| rng.gen_range(-1.0..1.0));
+        let (fast, naive) = (a.strassen_mul(&b).unwrap(), multiply(&a, &b));
+        for (x, y) in fast.iter().zip(naive.iter()) {
+            assert!((x - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    /// Powers of random matrices are equal to repeated products, modular powers are equal to
+    /// remainders of powers
+    fn pow_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let n: usize = rng.gen_range(1..=5);
+            let a: Matrix<u64> = Matrix::from_fn(n, n, |_, _| rng.gen_range(0..10));
+            let mut power: Matrix<u64> = Matrix::identity(n);
+            for k in 0..8 {
+                assert_eq!(a.pow(k), Some(power.clone()));
+                let remainders: Matrix<u64> = Matrix::from_fn(n, n, |r, c| power[r][c] % 97);
+                assert_eq!(a.pow_mod(k, 97), Some(remainders));
+                power = multiply(&power, &a);
+            }
+        }
+        assert!(random_matrix(2, 3, 1).pow(2).is_none());
+        assert_eq!(
+            Matrix::<u64>::identity(2).pow_mod(0, 1),
+            Some(Matrix::new(2, 2))
+        );
+    }
+
+    #[test]
+    /// `i8` matrices, which support elimination, also have identity and powers
+    fn i8_identity_pow_test() {
+        let identity: Matrix<i8> = Matrix::identity(2);
+        assert_eq!(identity[0], [1, 0]);
+        assert_eq!(identity[1], [0, 1]);
+        assert_eq!(identity.determinant(), 1);
+
+        let a: Matrix<i8> = Matrix::from_vec(vec![vec![1, 1], vec![1, 0]]).unwrap();
+        assert_eq!(a.pow(0), Some(identity));
+        assert_eq!(a.pow(5), Matrix::from_vec(vec![vec![8, 5], vec![5, 3]]));
+        assert_eq!(a.pow(5).unwrap().inverse(), a.inverse().unwrap().pow(5));
+    }
+
+    #[test]
+    /// Number of walks of length `k` in a directed graph is an element of power of adjacency
+    /// matrix, compared with dynamic programming over walk lengths
+    fn walks_count_test() {
+        let edges: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 0), (0, 2), (2, 2), (3, 1)];
+        let n: usize = 4;
+        let mut adjacency: Matrix<u64> = Matrix::new(n, n);
+        for &(u, v) in edges.iter() {
+            adjacency.set(u, v, 1);
+        }
+
+        // walks[v] is number of walks of current length from vertex 3 to v
+        let mut walks: Vec<u64> = vec![0, 0, 0, 1];
+        for k in 0..30 {
+            assert_eq!(&adjacency.pow(k).unwrap()[3], &walks[..]);
+            let mut next: Vec<u64> = vec![0; n];
+            for &(u, v) in edges.iter() {
+                next[v] += walks[u];
+            }
+            walks = next;
+        }
+    }
+
+    #[test]
+    /// Fibonacci and tribonacci numbers compared with iterative computation
+    fn linear_recurrence_test() {
+        let (mut a, mut b): (u64, u64) = (0, 1);
+        for n in 0..90 {
+            assert_eq!(linear_recurrence(&[1u64, 1], &[0, 1], n), a);
+            (a, b) = (b, a + b);
+        }
+
+        let mut tribonacci: Vec<i64> = vec![0, 0, 1];
+        for n in 3..60 {
+            tribonacci.push(tribonacci[n - 1] + tribonacci[n - 2] + tribonacci[n - 3]);
+        }
+        for (n, &x) in tribonacci.iter().enumerate() {
+            assert_eq!(linear_recurrence(&[1i64, 1, 1], &[0, 0, 1], n as u64), x);
+        }
+
+        // Alternating recurrence a[i] = 2 * a[i - 2] - a[i - 1] with f64 values
+        assert_eq!(linear_recurrence(&[-1.0, 2.0], &[1.0, 1.0], 40), 1.0);
+    }
+
+    #[test]
+    /// Modular recurrences compared with iterative computation and with Pisano period
+    fn linear_recurrence_mod_test() {
+        const MOD: u64 = 1_000_000_007;
+        let (mut a, mut b): (u64, u64) = (0, 1);
+        for n in 0..1000 {
+            assert_eq!(linear_recurrence_mod(&[1, 1], &[0, 1], n, MOD), a);
+            (a, b) = (b, (a + b) % MOD);
+        }
+
+        // Fibonacci numbers modulo 10 are periodic with period 60
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let n: u64 = rng.gen_range(1_000_000_000_000..u64::MAX);
+            assert_eq!(
+                linear_recurrence_mod(&[1, 1], &[0, 1], n, 10),
+                linear_recurrence_mod(&[1, 1], &[0, 1], n % 60, 10)
+            );
+        }
+
+        // Large modulo doesn't overflow
+        let x: u64 =
+            linear_recurrence_mod(&[u64::MAX - 1, 3], &[5, u64::MAX - 2], 1 << 40, u64::MAX);
+        assert!(x < u64::MAX);
+        assert_eq!(linear_recurrence_mod(&[1, 1], &[7, 8], 0, 5), 2);
+    }
+
+    #[test]
+    /// Operators on references and in place, shape errors of operations on two matrices
+    fn operators_test() {
+        let a: Matrix<i64> = random_matrix(3, 4, 100);
+        let b: Matrix<i64> = random_matrix(3, 4, 100);
+        let sum: Matrix<i64> = (&a + &b).unwrap();
+        assert_eq!(&(&sum - &b).unwrap(), &a);
+        assert_eq!(-&a, &a * -1);
+
+        let mut c: Matrix<i64> = a.clone();
+        c -= &b;
+        c += b.clone();
+        c *= 2;
+        assert_eq!(c, (&a + &a).unwrap());
+        c *= &b.transpose();
+        assert_eq!(c.shape(), (3, 3));
+
+        let error = ShapeMismatch {
+            expected: (3, 4),
+            got: (4, 3),
+        };
+        assert_eq!(&a + &b.transpose(), Err(error));
+        assert_eq!(a.clone() - b.transpose(), Err(error));
+        assert_eq!(
+            &a * &b,
+            Err(ShapeMismatch {
+                expected: (4, 4),
+                got: (3, 4)
+            })
+        );
+        assert_eq!(
+            a.strassen_mul(&a),
+            Err(ShapeMismatch {
+                expected: (3, 3),
+                got: (3, 4)
+            })
+        );
+        assert_eq!(
+            a.concat_vertical(&c),
+            Err(ShapeMismatch {
+                expected: (3, 4),
+                got: (3, 3)
+            })
+        );
+        assert_eq!(
+            Matrix::<u64>::identity(2).mul_mod(&Matrix::identity(3), 7),
+            Err(ShapeMismatch {
+                expected: (2, 3),
+                got: (3, 3)
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix shape mismatch: expected 2x2, got 3x2")]
+    /// Product in place panics, if shapes don't match
+    fn mul_assign_shape_mismatch_test() {
+        let mut a: Matrix<i32> = Matrix::identity(2);
+        a *= Matrix::new(3, 2);
+    }
+}
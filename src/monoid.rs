@@ -0,0 +1,281 @@
+/// [Monoid](https://en.wikipedia.org/wiki/Monoid) - set of elements `T` with an associative
+/// combination operation and an identity element.
+///
+/// Monoid is passed to data structures (such as `SegmentTree`) as a value, so it can carry its own
+/// state (for example, a modulo or a captured closure).
+pub trait Monoid<T> {
+    /// Returns an identity element `i` such `combine(e, i) = combine(i, e) = e` for every `e`
+    fn identity(&self) -> T;
+
+    /// Combines two elements. Must be associative
+    fn combine(&self, a: &T, b: &T) -> T;
+}
+
+/// [Group](https://en.wikipedia.org/wiki/Group_(mathematics)) - monoid, where every element has
+/// an inverse element. Used by data structures, that compute results on a segment as a difference
+/// of prefix results (such as `Fenwick`).
+pub trait Group<T>: Monoid<T> {
+    /// Returns an inverse element `a'` such `combine(a, a') = combine(a', a) = identity`
+    fn inverse(&self, a: &T) -> T;
+}
+
+/// Idempotent monoid, where `combine(a, a) = a` for every `a`. Results of overlapping segments
+/// can be combined without double counting (used by `SparseTable`).
+pub trait Idempotent<T>: Monoid<T> {}
+
+/// Addition monoid, identity is `0`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sum;
+
+/// Multiplication monoid, identity is `1`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Product;
+
+/// Minimum monoid, identity is the largest value of a type
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Min;
+
+/// Maximum monoid, identity is the smallest value of a type
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Max;
+
+/// [Greatest Common Divisor](https://en.wikipedia.org/wiki/Greatest_common_divisor) monoid,
+/// identity is `0`. Result is non-negative, except for signed `gcd(MIN, 0)` and `gcd(MIN, MIN)`:
+/// `2^(bits - 1)` doesn't fit into the type, so it is returned as `MIN`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Gcd;
+
+/// Bitwise exclusive or monoid, identity is `0`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Xor;
+
+/// Monoid built from an identity element and a combination function (or closure)
+///
+/// # Fields:
+/// * `identity` - an identity element
+/// * `combine_fn` - a combination function, must be associative
+#[derive(Clone)]
+pub struct FnMonoid<T, F> {
+    identity: T,
+    combine_fn: F,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> FnMonoid<T, F> {
+    /// Constructs and returns monoid based on given identity element and combination function
+    ///
+    /// # Arguments:
+    /// * `identity` - An identity element, such as `0` for addition or `1` for multiplication
+    /// * `combine_fn` - A function or closure, which combines two elements
+    pub fn new(identity: T, combine_fn: F) -> FnMonoid<T, F> {
+        return FnMonoid {
+            identity,
+            combine_fn,
+        };
+    }
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> Monoid<T> for FnMonoid<T, F> {
+    fn identity(&self) -> T {
+        return self.identity.clone();
+    }
+
+    fn combine(&self, a: &T, b: &T) -> T {
+        return (self.combine_fn)(a, b);
+    }
+}
+
+/// Implements `Sum`, `Product`, `Min`, `Max` and `Xor` for integer types
+macro_rules! impl_integer_monoids {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid<$t> for Sum {
+                fn identity(&self) -> $t {
+                    return 0;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return *a + *b;
+                }
+            }
+
+            impl Monoid<$t> for Product {
+                fn identity(&self) -> $t {
+                    return 1;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return *a * *b;
+                }
+            }
+
+            impl Monoid<$t> for Min {
+                fn identity(&self) -> $t {
+                    return <$t>::MAX;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return *a.min(b);
+                }
+            }
+
+            impl Monoid<$t> for Max {
+                fn identity(&self) -> $t {
+                    return <$t>::MIN;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return *a.max(b);
+                }
+            }
+
+            impl Monoid<$t> for Xor {
+                fn identity(&self) -> $t {
+                    return 0;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return *a ^ *b;
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Gcd` for unsigned integer types
+macro_rules! impl_unsigned_gcd {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid<$t> for Gcd {
+                fn identity(&self) -> $t {
+                    return 0;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    let (mut a, mut b) = (*a, *b);
+                    while b != 0 {
+                        (a, b) = (b, a % b);
+                    }
+                    return a;
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Gcd` for signed integer types. GCD is computed on absolute values of unsigned type
+/// `$u`, so `MIN` never overflows. The only result, that doesn't fit into `$t`, is `2^(bits - 1)`
+/// (for example, `gcd(MIN, 0)`), it is returned as `MIN`, which is then combined as `2^(bits - 1)`
+macro_rules! impl_signed_gcd {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl Monoid<$t> for Gcd {
+                fn identity(&self) -> $t {
+                    return 0;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    let (mut a, mut b): ($u, $u) = (a.unsigned_abs(), b.unsigned_abs());
+                    while b != 0 {
+                        (a, b) = (b, a % b);
+                    }
+                    return a as $t;
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Sum`, `Product`, `Min` and `Max` for floating point types
+macro_rules! impl_float_monoids {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid<$t> for Sum {
+                fn identity(&self) -> $t {
+                    return 0.0;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return *a + *b;
+                }
+            }
+
+            impl Monoid<$t> for Product {
+                fn identity(&self) -> $t {
+                    return 1.0;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return *a * *b;
+                }
+            }
+
+            impl Monoid<$t> for Min {
+                fn identity(&self) -> $t {
+                    return <$t>::INFINITY;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return a.min(*b);
+                }
+            }
+
+            impl Monoid<$t> for Max {
+                fn identity(&self) -> $t {
+                    return <$t>::NEG_INFINITY;
+                }
+
+                fn combine(&self, a: &$t, b: &$t) -> $t {
+                    return a.max(*b);
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Idempotent` for `Min`, `Max` and `Gcd`
+macro_rules! impl_idempotent {
+    (min_max: $($ord:ty),*; gcd: $($int:ty),*) => {
+        $(
+            impl Idempotent<$ord> for Min {}
+            impl Idempotent<$ord> for Max {}
+        )*
+        $(
+            impl Idempotent<$int> for Gcd {}
+        )*
+    };
+}
+
+/// Implements `Group` for `Sum` of signed numeric types and for `Xor`
+macro_rules! impl_groups {
+    (sum: $($signed:ty),*; xor: $($int:ty),*) => {
+        $(
+            impl Group<$signed> for Sum {
+                fn inverse(&self, a: &$signed) -> $signed {
+                    return -*a;
+                }
+            }
+        )*
+        $(
+            impl Group<$int> for Xor {
+                fn inverse(&self, a: &$int) -> $int {
+                    return *a;
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_monoids!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_unsigned_gcd!(u8, u16, u32, u64, u128, usize);
+impl_signed_gcd!(
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+impl_float_monoids!(f32, f64);
+impl_groups!(
+    sum: i8, i16, i32, i64, i128, isize, f32, f64;
+    xor: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+impl_idempotent!(
+    min_max: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64;
+    gcd: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
@@ -0,0 +1,281 @@
+use crate::monoid::{Monoid, Sum};
+
+/// Node of persistent segment tree
+///
+/// # Fields:
+/// * `value` - result of `combine` for node's segment
+/// * `left` - index of left child in `nodes` (unused for leaves)
+/// * `right` - index of right child in `nodes` (unused for leaves)
+#[derive(Clone)]
+struct Node<T> {
+    value: T,
+    left: usize,
+    right: usize,
+}
+
+/// [Persistent](https://en.wikipedia.org/wiki/Persistent_data_structure) segment tree, that allows
+/// to compute associative function on a segment of any previous version of the array.
+///
+/// Every `set` creates a new version and returns its number. New version shares all unchanged
+/// nodes with the version it was created from, so only O(logN) nodes are allocated per `set`.
+/// Any version can be changed, which creates a branch, so undoing edits is just querying an older
+/// version.
+///
+/// Described [here](https://cp-algorithms.com/data_structures/segment_tree.html#preserving-the-history-of-its-values-persistent-segment-tree).
+///
+/// Memory complexity - O(N + QlogN), where Q is a number of `set` calls
+///
+/// Time complexity:
+/// * `get` - O(logN)
+/// * `set` - O(logN)
+pub struct PersistentSegmentTree<T: Clone, M: Monoid<T>> {
+    /// `nodes` is a vector containing nodes of all versions
+    nodes: Vec<Node<T>>,
+    /// `roots` is a vector containing root node index of every version, version `0` is initial
+    roots: Vec<usize>,
+    /// `n` is initial vector's size
+    n: usize,
+    /// `monoid` provides an identity element and an associative combination function, such as
+    /// addition or gcd
+    monoid: M,
+}
+
+/// Persistent segment tree generic implementation.
+///
+/// Type `T` must support `Clone` for moving elements from nodes.
+impl<T: Clone, M: Monoid<T>> PersistentSegmentTree<T, M> {
+    /// Constructs and returns persistent segment tree based on given vector and monoid. Initial
+    /// vector is version `0`.
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - An initial vector
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Gcd`
+    pub fn new(v: &[T], monoid: M) -> PersistentSegmentTree<T, M> {
+        let mut seg_tree: PersistentSegmentTree<T, M> = PersistentSegmentTree {
+            nodes: Vec::with_capacity(2 * v.len()),
+            roots: Vec::new(),
+            n: v.len(),
+            monoid,
+        };
+
+        // Building segment tree, root of empty tree is a single identity leaf
+        let root: usize = if seg_tree.n > 0 {
+            seg_tree.__build(v, 0, seg_tree.n)
+        } else {
+            let identity: T = seg_tree.monoid.identity();
+            seg_tree.__new_leaf(identity)
+        };
+        seg_tree.roots.push(root);
+
+        return seg_tree;
+    }
+
+    /// Returns number of versions
+    pub fn versions(&self) -> usize {
+        return self.roots.len();
+    }
+
+    /// Creates new node from two children and returns its index
+    fn __new_parent(&mut self, left: usize, right: usize) -> usize {
+        let value: T = self
+            .monoid
+            .combine(&self.nodes[left].value, &self.nodes[right].value);
+        self.nodes.push(Node { value, left, right });
+        return self.nodes.len() - 1;
+    }
+
+    /// Creates new leaf and returns its index
+    fn __new_leaf(&mut self, value: T) -> usize {
+        self.nodes.push(Node {
+            value,
+            left: 0,
+            right: 0,
+        });
+        return self.nodes.len() - 1;
+    }
+
+    /// Builds subtree for `v[tree_l..tree_r]` and returns index of its root
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - initial vector
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    fn __build(&mut self, v: &[T], tree_l: usize, tree_r: usize) -> usize {
+        // Node contains one element case
+        if tree_r - tree_l == 1 {
+            return self.__new_leaf(v[tree_l].clone());
+        }
+
+        let m: usize = (tree_l + tree_r) / 2;
+        let left: usize = self.__build(v, tree_l, m);
+        let right: usize = self.__build(v, m, tree_r);
+        return self.__new_parent(left, right);
+    }
+
+    /// Returns `combine` result for `v[lq..rq]` query
+    ///
+    /// # Arguments:
+    ///
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    fn __get(
+        &self,
+        node: usize,
+        tree_l: usize,
+        tree_r: usize,
+        query_l: usize,
+        query_r: usize,
+    ) -> T {
+        if query_l <= tree_l && tree_r <= query_r {
+            // tree_l..tree_r completely lies in query_l..query_r
+            return self.nodes[node].value.clone();
+        } else if tree_r <= query_l || query_r <= tree_l {
+            // tree_l..tree_r doesn't intersect with query_l..query_r
+            return self.monoid.identity();
+        }
+
+        let m: usize = (tree_l + tree_r) / 2;
+        let l_child: T = self.__get(self.nodes[node].left, tree_l, m, query_l, query_r);
+        let r_child: T = self.__get(self.nodes[node].right, m, tree_r, query_l, query_r);
+        return self.monoid.combine(&l_child, &r_child);
+    }
+
+    /// Friendly interface of `__get` with version and query bounds assert
+    ///
+    /// # Arguments:
+    /// * `version` - number of version
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, version: usize, query_l: usize, query_r: usize) -> T {
+        // Assert version and query bounds
+        assert!(version < self.roots.len());
+        assert!(query_l < query_r && query_r <= self.n);
+        return self.__get(self.roots[version], 0, self.n, query_l, query_r);
+    }
+
+    /// Creates copy of path from `node` to `i`-th leaf with `new_val` in the leaf and returns
+    /// index of new node
+    ///
+    /// # Arguments:
+    /// * `node` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    /// * `i` - index of element in initial array
+    /// * `new_val` - new value of `i`-th element
+    fn __set(&mut self, node: usize, tree_l: usize, tree_r: usize, i: usize, new_val: T) -> usize {
+        // Node contains one element and its index is `i`
+        if tree_r - tree_l == 1 {
+            return self.__new_leaf(new_val);
+        }
+
+        // Only one child changes, another one is shared with previous version
+        let m: usize = (tree_l + tree_r) / 2;
+        let (mut left, mut right) = (self.nodes[node].left, self.nodes[node].right);
+        if i < m {
+            left = self.__set(left, tree_l, m, i, new_val);
+        } else {
+            right = self.__set(right, m, tree_r, i, new_val);
+        }
+        return self.__new_parent(left, right);
+    }
+
+    /// Sets `i`-th element of given version to `new_val`. Given version stays unchanged, the
+    /// result is stored as a new version.
+    ///
+    /// Returns number of new version.
+    ///
+    /// # Arguments:
+    /// * `version` - number of version, which will be changed
+    /// * `i` - index of element in initial array
+    /// * `new_val` - new value of `i`-th element
+    pub fn set(&mut self, version: usize, i: usize, new_val: T) -> usize {
+        // Assert version and `i` bounds
+        assert!(version < self.roots.len());
+        assert!(i < self.n);
+
+        let root: usize = self.__set(self.roots[version], 0, self.n, i, new_val);
+        self.roots.push(root);
+        return self.roots.len() - 1;
+    }
+}
+
+/// Data structure answering "k-th smallest element of `v[l..r]`" queries, built on top of
+/// `PersistentSegmentTree`.
+///
+/// Version `i` of the tree counts occurrences of every (compressed) value among `v[0..i]`, so
+/// counts of `v[l..r]` are the difference of versions `r` and `l`. Query descends in both versions
+/// simultaneously.
+///
+/// Memory complexity - O(NlogN)
+///
+/// Time complexity:
+/// * `new` - O(NlogN)
+/// * `kth_smallest` - O(logN)
+pub struct RangeKthSmallest<T: Ord + Clone> {
+    /// `values` is a sorted vector of distinct elements of initial vector
+    values: Vec<T>,
+    /// `counts` is a persistent tree over `values` indices, version `i` contains counts of
+    /// `v[0..i]`
+    counts: PersistentSegmentTree<usize, Sum>,
+}
+
+impl<T: Ord + Clone> RangeKthSmallest<T> {
+    /// Constructs and returns structure based on given vector
+    ///
+    /// # Arguments:
+    /// * `v` - An initial vector
+    pub fn new(v: &[T]) -> RangeKthSmallest<T> {
+        // Compress values into indices of sorted distinct values
+        let mut values: Vec<T> = v.to_vec();
+        values.sort();
+        values.dedup();
+
+        let mut counts = PersistentSegmentTree::new(&vec![0; values.len()], Sum);
+        for (i, el) in v.iter().enumerate() {
+            let pos: usize = values.binary_search(el).unwrap();
+            let old_count: usize = counts.get(i, pos, pos + 1);
+            counts.set(i, pos, old_count + 1);
+        }
+
+        return RangeKthSmallest { values, counts };
+    }
+
+    /// Returns `k`-th smallest (`0`-indexed) element of `v[query_l..query_r]`
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    /// * `k` - index of element in sorted segment, must be less than `query_r - query_l`
+    pub fn kth_smallest(&self, query_l: usize, query_r: usize, k: usize) -> &T {
+        // Assert query bounds
+        assert!(query_l < query_r && query_r < self.counts.versions());
+        assert!(k < query_r - query_l);
+
+        let nodes = &self.counts.nodes;
+        let mut l_node: usize = self.counts.roots[query_l];
+        let mut r_node: usize = self.counts.roots[query_r];
+        let (mut tree_l, mut tree_r) = (0, self.counts.n);
+        let mut k: usize = k;
+        while tree_r - tree_l > 1 {
+            let m: usize = (tree_l + tree_r) / 2;
+            // Number of elements of the segment in the left half of values
+            let left_count: usize =
+                nodes[nodes[r_node].left].value - nodes[nodes[l_node].left].value;
+            if k < left_count {
+                (l_node, r_node) = (nodes[l_node].left, nodes[r_node].left);
+                tree_r = m;
+            } else {
+                (l_node, r_node) = (nodes[l_node].right, nodes[r_node].right);
+                tree_l = m;
+                k -= left_count;
+            }
+        }
+
+        return &self.values[tree_l];
+    }
+}
@@ -0,0 +1,16 @@
+//! Re-exports of the core types and traits of the crate.
+//!
+//! ```
+//! use rust_aads::prelude::*;
+//! ```
+
+pub use crate::algebra::{Matrix, One, ShapeMismatch};
+pub use crate::dsu::{Dsu, DsuWithData, RollbackDsu, RollbackDsuWithData};
+pub use crate::dynamic_segtree::{DynamicSegmentTree, Key, LazyDynamicSegmentTree, RangeAdd};
+pub use crate::fenwick::{Fenwick, Fenwick2D, RangeFenwick};
+pub use crate::heap::IndexedHeap;
+pub use crate::monoid::{FnMonoid, Gcd, Group, Idempotent, Max, Min, Monoid, Product, Sum, Xor};
+pub use crate::persistent_segtree::{PersistentSegmentTree, RangeKthSmallest};
+pub use crate::segtree::{BottomUpSegmentTree, LazySegmentTree, SegmentTree};
+pub use crate::segtree_2d::SegmentTree2D;
+pub use crate::sparse_table::{DisjointSparseTable, SparseTable};
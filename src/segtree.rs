@@ -1,3 +1,5 @@
+use crate::monoid::Monoid;
+
 /// Segment tree structure generic, that allows to efficiently compute associative function on a
 /// segment.
 ///
@@ -9,58 +11,50 @@
 /// Time complexity:
 /// * `get` - O(logN)
 /// * `set` - O(logN)
-pub struct SegmentTree<T: Eq + Copy> {
+/// * `max_right`, `min_left` - O(logN)
+pub struct SegmentTree<T: Clone, M: Monoid<T>> {
     /// `tree` is a vector containing each node's value. `tree[0]` is a root node
     tree: Vec<T>,
     /// `n` is initial vector's size
     n: usize,
-    /// `identity` is an element `i` such `combine(e, i) = e` for every `e`
-    identity: T,
-    /// `combine_fn` is function of combination such as addition or gcd.
-    /// Must be associative
-    combine_fn: fn(T, T) -> T,
+    /// `monoid` provides an identity element and an associative combination function, such as
+    /// addition or gcd
+    monoid: M,
 }
 
 /// Segment tree generic implementation.
 ///
-/// Type `T` must support `Eq` for checking equality with identity.
-///
-/// Type `T` must support `Copy` for moving elements from vector tree and creating initial tree
+/// Type `T` must support `Clone` for moving elements from vector tree and creating initial tree
 /// filled with identities.
-impl<T: Eq + Copy> SegmentTree<T> {
-    /// Constructs and returns segment tree based on given vector, operation and identity element
+impl<T: Clone, M: Monoid<T>> SegmentTree<T, M> {
+    /// Constructs and returns segment tree based on given vector and monoid
     ///
     /// # Arguments:
     ///
     /// * `v` - An initial vector
-    /// * `identity` - An identity element, such as `0` for addition or `1` for multiplication
-    /// * `combine_fn` - A function, which will be computed on query. Arguments are 2 `T`
-    /// instances and return type is `T`
-    pub fn new(v: &Vec<T>, identity: T, combine_fn: fn(T, T) -> T) -> SegmentTree<T> {
-        let mut seg_tree: SegmentTree<T> = SegmentTree {
-            /// `tree` initially is a vector filled with
-            /// `identity` of size `4 * vec.len`
-            tree: vec![identity; 4 * v.len()],
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Gcd`
+    pub fn new(v: &[T], monoid: M) -> SegmentTree<T, M> {
+        let mut seg_tree: SegmentTree<T, M> = SegmentTree {
+            // `tree` initially is a vector filled with
+            // `identity` of size `4 * vec.len`
+            tree: vec![monoid.identity(); 4 * v.len()],
             n: v.len(),
-            identity,
-            combine_fn,
+            monoid,
         };
 
         // Building segment tree
-        seg_tree.__build(v, 0, 0, seg_tree.n);
+        if seg_tree.n > 0 {
+            seg_tree.__build(v, 0, 0, seg_tree.n);
+        }
 
         return seg_tree;
     }
 
-    /// Combines two elements with handling `identity` cases
-    fn __combine(&self, a: T, b: T) -> T {
-        return if a == self.identity {
-            b
-        } else if b == self.identity {
-            a
-        } else {
-            (self.combine_fn)(a, b)
-        };
+    /// Recomputes node's value from its children
+    fn __pull(&mut self, tree_id: usize) {
+        self.tree[tree_id] = self
+            .monoid
+            .combine(&self.tree[2 * tree_id + 1], &self.tree[2 * tree_id + 2]);
     }
 
     /// Builds subtree based on its children
@@ -72,11 +66,11 @@ impl<T: Eq + Copy> SegmentTree<T> {
     /// * `tree_l` - left bound of node in array
     /// * `tree_r` - right bound of node in array (non-inclusive)
     ///
-    /// In other words, `tree[tree_id]` contains result of `combine_fn` for `v[tree_l..tree_r]`
-    fn __build(&mut self, v: &Vec<T>, tree_id: usize, tree_l: usize, tree_r: usize) {
+    /// In other words, `tree[tree_id]` contains result of `combine` for `v[tree_l..tree_r]`
+    fn __build(&mut self, v: &[T], tree_id: usize, tree_l: usize, tree_r: usize) {
         // Node contains one element case
         if tree_r - tree_l == 1 {
-            self.tree[tree_id] = v[tree_l];
+            self.tree[tree_id] = v[tree_l].clone();
             return;
         }
 
@@ -85,10 +79,10 @@ impl<T: Eq + Copy> SegmentTree<T> {
         let m: usize = (tree_l + tree_r) / 2;
         self.__build(v, 2 * tree_id + 1, tree_l, m);
         self.__build(v, 2 * tree_id + 2, m, tree_r);
-        self.tree[tree_id] = self.__combine(self.tree[2 * tree_id + 1], self.tree[2 * tree_id + 2]);
+        self.__pull(tree_id);
     }
 
-    /// Returns `combine_fn` result for `v[lq..rq]` query
+    /// Returns `combine` result for `v[lq..rq]` query
     ///
     /// # Arguments:
     ///
@@ -107,17 +101,17 @@ impl<T: Eq + Copy> SegmentTree<T> {
     ) -> T {
         if query_l <= tree_l && tree_r <= query_r {
             // tree_l..tree_r completely lies in query_l..query_r
-            return self.tree[tree_id];
+            return self.tree[tree_id].clone();
         } else if tree_r <= query_l || query_r <= tree_l {
             // tree_l..tree_r doesn't intersect with query_l..query_r
-            return self.identity;
+            return self.monoid.identity();
         }
 
         // Divide tree_l..tree_r into two halves and get answer from two children of a node
         let m: usize = (tree_l + tree_r) / 2;
         let l_child: T = self.__get(2 * tree_id + 1, tree_l, m, query_l, query_r);
         let r_child: T = self.__get(2 * tree_id + 2, m, tree_r, query_l, query_r);
-        return self.__combine(l_child, r_child);
+        return self.monoid.combine(&l_child, &r_child);
     }
 
     /// Friendly interface of `__get` with query bounds assert
@@ -155,7 +149,361 @@ impl<T: Eq + Copy> SegmentTree<T> {
             // `i`-th element lies in right child
             self.__set(2 * tree_id + 2, m, tree_r, i, new_val);
         }
-        self.tree[tree_id] = self.__combine(self.tree[2 * tree_id + 1], self.tree[2 * tree_id + 2]);
+        self.__pull(tree_id);
+    }
+
+    /// Friendly interface of `__set` with `i` assert
+    ///
+    /// # Arguments:
+    /// * `i` - index of element in initial array
+    /// * `new_val` - new value of `i`-th element
+    pub fn set(&mut self, i: usize, new_val: T) {
+        assert!(i < self.n); // Asserts `i` bounds
+        self.__set(0, 0, self.n, i, new_val);
+    }
+
+    /// Descends from the node to find the first position, where `pred` of accumulated result
+    /// becomes `false`
+    ///
+    /// # Arguments:
+    /// * `tree_id` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `pred` - predicate
+    /// * `acc` - result of `combine` for `v[query_l..tree_l]`, updated with nodes that satisfy
+    ///   `pred`
+    ///
+    /// Returns `Some(r)` if `pred` fails on `v[query_l..=r]` and `None` if it holds for the whole
+    /// node
+    fn __max_right<F: Fn(&T) -> bool>(
+        &self,
+        tree_id: usize,
+        tree_l: usize,
+        tree_r: usize,
+        query_l: usize,
+        pred: &F,
+        acc: &mut T,
+    ) -> Option<usize> {
+        if tree_r <= query_l {
+            // tree_l..tree_r lies to the left of query
+            return None;
+        }
+        if query_l <= tree_l {
+            // tree_l..tree_r completely lies in query, try to take the whole node
+            let combined: T = self.monoid.combine(acc, &self.tree[tree_id]);
+            if pred(&combined) {
+                *acc = combined;
+                return None;
+            }
+            if tree_r - tree_l == 1 {
+                return Some(tree_l);
+            }
+        }
+
+        // Answer lies in the left child or in the right one
+        let m: usize = (tree_l + tree_r) / 2;
+        return match self.__max_right(2 * tree_id + 1, tree_l, m, query_l, pred, acc) {
+            Some(r) => Some(r),
+            None => self.__max_right(2 * tree_id + 2, m, tree_r, query_l, pred, acc),
+        };
+    }
+
+    /// Returns the largest `r` such `pred(combine(v[l..r]))` is `true`.
+    ///
+    /// `pred` must be monotone (if it is `true` on a segment, then it is `true` on every its
+    /// prefix) and `pred(identity)` must be `true`.
+    ///
+    /// Time complexity - O(logN)
+    ///
+    /// # Arguments:
+    /// * `l` - left bound of segment, at most `n`
+    /// * `pred` - predicate on result of `combine`
+    pub fn max_right<F: Fn(&T) -> bool>(&self, l: usize, pred: F) -> usize {
+        assert!(l <= self.n); // Asserts `l` bounds
+        assert!(pred(&self.monoid.identity()));
+        if l == self.n {
+            return self.n;
+        }
+
+        let mut acc: T = self.monoid.identity();
+        return self
+            .__max_right(0, 0, self.n, l, &pred, &mut acc)
+            .unwrap_or(self.n);
+    }
+
+    /// Descends from the node to find the last position, where `pred` of accumulated result
+    /// becomes `false`
+    ///
+    /// # Arguments:
+    /// * `tree_id` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    /// * `query_r` - right bound of query (non-inclusive)
+    /// * `pred` - predicate
+    /// * `acc` - result of `combine` for `v[tree_r..query_r]`, updated with nodes that satisfy
+    ///   `pred`
+    ///
+    /// Returns `Some(l)` if `pred` fails on `v[l - 1..query_r]` and `None` if it holds for the
+    /// whole node
+    fn __min_left<F: Fn(&T) -> bool>(
+        &self,
+        tree_id: usize,
+        tree_l: usize,
+        tree_r: usize,
+        query_r: usize,
+        pred: &F,
+        acc: &mut T,
+    ) -> Option<usize> {
+        if query_r <= tree_l {
+            // tree_l..tree_r lies to the right of query
+            return None;
+        }
+        if tree_r <= query_r {
+            // tree_l..tree_r completely lies in query, try to take the whole node
+            let combined: T = self.monoid.combine(&self.tree[tree_id], acc);
+            if pred(&combined) {
+                *acc = combined;
+                return None;
+            }
+            if tree_r - tree_l == 1 {
+                return Some(tree_r);
+            }
+        }
+
+        // Answer lies in the right child or in the left one
+        let m: usize = (tree_l + tree_r) / 2;
+        return match self.__min_left(2 * tree_id + 2, m, tree_r, query_r, pred, acc) {
+            Some(l) => Some(l),
+            None => self.__min_left(2 * tree_id + 1, tree_l, m, query_r, pred, acc),
+        };
+    }
+
+    /// Returns the smallest `l` such `pred(combine(v[l..r]))` is `true`.
+    ///
+    /// `pred` must be monotone (if it is `true` on a segment, then it is `true` on every its
+    /// suffix) and `pred(identity)` must be `true`.
+    ///
+    /// Time complexity - O(logN)
+    ///
+    /// # Arguments:
+    /// * `r` - right bound of segment (non-inclusive), at most `n`
+    /// * `pred` - predicate on result of `combine`
+    pub fn min_left<F: Fn(&T) -> bool>(&self, r: usize, pred: F) -> usize {
+        assert!(r <= self.n); // Asserts `r` bounds
+        assert!(pred(&self.monoid.identity()));
+        if r == 0 {
+            return 0;
+        }
+
+        let mut acc: T = self.monoid.identity();
+        return self
+            .__min_left(0, 0, self.n, r, &pred, &mut acc)
+            .unwrap_or(0);
+    }
+}
+
+/// Segment tree with [lazy propagation](https://cp-algorithms.com/data_structures/segment_tree.html#range-updates-lazy-propagation),
+/// that allows to apply an update to every element of a segment and to compute associative
+/// function on a segment.
+///
+/// Updates are elements of type `U` (for example, "add `x`" or "assign `x`"). Update is stored
+/// in a node and pushed to its children only when they are visited.
+///
+/// Memory complexity - O(N)
+///
+/// Time complexity:
+/// * `get` - O(logN)
+/// * `set` - O(logN)
+/// * `update` - O(logN)
+pub struct LazySegmentTree<
+    T: Clone,
+    U: Clone,
+    M: Monoid<T>,
+    A: Fn(&T, &U, usize) -> T = fn(&T, &U, usize) -> T,
+    C: Fn(&U, &U) -> U = fn(&U, &U) -> U,
+> {
+    /// `tree` is a vector containing each node's value. `tree[0]` is a root node
+    tree: Vec<T>,
+    /// `lazy` is a vector containing each node's pending update, which is already applied to the
+    /// node itself, but not to its children
+    lazy: Vec<Option<U>>,
+    /// `n` is initial vector's size
+    n: usize,
+    /// `monoid` provides an identity element and an associative combination function, such as
+    /// addition or gcd
+    monoid: M,
+    /// `apply_fn` is function that applies update to a value of node, which contains
+    /// `len` elements
+    apply_fn: A,
+    /// `compose_fn` is function that composes pending update (first argument) with a newer one
+    /// (second argument), result must be equal to applying first update and then second one
+    compose_fn: C,
+}
+
+/// Lazy segment tree generic implementation.
+///
+/// Type `T` must support `Clone` for moving elements from vector tree and creating initial tree
+/// filled with identities.
+///
+/// Type `U` must support `Clone` for pushing updates from node to its children.
+///
+/// `A` and `C` may be closures, so updates can capture state (for example, a modulus).
+impl<T: Clone, U: Clone, M: Monoid<T>, A: Fn(&T, &U, usize) -> T, C: Fn(&U, &U) -> U>
+    LazySegmentTree<T, U, M, A, C>
+{
+    /// Constructs and returns lazy segment tree based on given vector, monoid and update
+    /// operations
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - An initial vector
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Min`
+    /// * `apply_fn` - A function, which applies update to a node. Arguments are node's value,
+    ///   update and number of elements in a node, return type is `T`. For example, "add `x`"
+    ///   update of sum is `value + x * len`
+    /// * `compose_fn` - A function, which composes older update with a newer one. For example,
+    ///   "add `x`" updates compose as `old + new` and "assign `x`" updates compose as `new`
+    pub fn new(v: &[T], monoid: M, apply_fn: A, compose_fn: C) -> LazySegmentTree<T, U, M, A, C> {
+        let mut seg_tree: LazySegmentTree<T, U, M, A, C> = LazySegmentTree {
+            tree: vec![monoid.identity(); 4 * v.len()],
+            lazy: vec![None; 4 * v.len()],
+            n: v.len(),
+            monoid,
+            apply_fn,
+            compose_fn,
+        };
+
+        // Building segment tree
+        if seg_tree.n > 0 {
+            seg_tree.__build(v, 0, 0, seg_tree.n);
+        }
+
+        return seg_tree;
+    }
+
+    /// Builds subtree based on its children
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - initial vector
+    /// * `tree_id` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    fn __build(&mut self, v: &[T], tree_id: usize, tree_l: usize, tree_r: usize) {
+        // Node contains one element case
+        if tree_r - tree_l == 1 {
+            self.tree[tree_id] = v[tree_l].clone();
+            return;
+        }
+
+        let m: usize = (tree_l + tree_r) / 2;
+        self.__build(v, 2 * tree_id + 1, tree_l, m);
+        self.__build(v, 2 * tree_id + 2, m, tree_r);
+        self.__pull(tree_id);
+    }
+
+    /// Recomputes node's value from its children
+    fn __pull(&mut self, tree_id: usize) {
+        self.tree[tree_id] = self
+            .monoid
+            .combine(&self.tree[2 * tree_id + 1], &self.tree[2 * tree_id + 2]);
+    }
+
+    /// Applies update to the node, which contains `len` elements, and remembers it as pending
+    fn __apply(&mut self, tree_id: usize, len: usize, update: &U) {
+        self.tree[tree_id] = (self.apply_fn)(&self.tree[tree_id], update, len);
+        self.lazy[tree_id] = match &self.lazy[tree_id] {
+            Some(pending) => Some((self.compose_fn)(pending, update)),
+            None => Some(update.clone()),
+        };
+    }
+
+    /// Pushes pending update of the node to its children
+    ///
+    /// # Arguments:
+    ///
+    /// * `tree_id` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    fn __push(&mut self, tree_id: usize, tree_l: usize, tree_r: usize) {
+        if let Some(update) = self.lazy[tree_id].take() {
+            let m: usize = (tree_l + tree_r) / 2;
+            self.__apply(2 * tree_id + 1, m - tree_l, &update);
+            self.__apply(2 * tree_id + 2, tree_r - m, &update);
+        }
+    }
+
+    /// Returns `combine` result for `v[lq..rq]` query
+    ///
+    /// # Arguments:
+    ///
+    /// * `tree_id` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    fn __get(
+        &mut self,
+        tree_id: usize,
+        tree_l: usize,
+        tree_r: usize,
+        query_l: usize,
+        query_r: usize,
+    ) -> T {
+        if query_l <= tree_l && tree_r <= query_r {
+            // tree_l..tree_r completely lies in query_l..query_r
+            return self.tree[tree_id].clone();
+        } else if tree_r <= query_l || query_r <= tree_l {
+            // tree_l..tree_r doesn't intersect with query_l..query_r
+            return self.monoid.identity();
+        }
+
+        // Children must be up to date before descending into them
+        self.__push(tree_id, tree_l, tree_r);
+        let m: usize = (tree_l + tree_r) / 2;
+        let l_child: T = self.__get(2 * tree_id + 1, tree_l, m, query_l, query_r);
+        let r_child: T = self.__get(2 * tree_id + 2, m, tree_r, query_l, query_r);
+        return self.monoid.combine(&l_child, &r_child);
+    }
+
+    /// Friendly interface of `__get` with query bounds assert.
+    ///
+    /// Takes `&mut self`, because pending updates are pushed down on the way.
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&mut self, query_l: usize, query_r: usize) -> T {
+        // Assert query bounds
+        assert!(query_l < query_r && query_r <= self.n);
+        return self.__get(0, 0, self.n, query_l, query_r);
+    }
+
+    /// Sets `i`-th element of segment tree to `new_val` and updates tree
+    ///
+    /// # Arguments:
+    /// * `tree_id` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    /// * `i` - index of element in initial array
+    /// * `new_val` - new value of `i`-th element
+    fn __set(&mut self, tree_id: usize, tree_l: usize, tree_r: usize, i: usize, new_val: T) {
+        // Node contains one element and its index is `i`
+        if tree_r - tree_l == 1 {
+            self.tree[tree_id] = new_val;
+            self.lazy[tree_id] = None;
+            return;
+        }
+
+        self.__push(tree_id, tree_l, tree_r);
+        let m: usize = (tree_l + tree_r) / 2;
+        if i < m {
+            self.__set(2 * tree_id + 1, tree_l, m, i, new_val);
+        } else {
+            self.__set(2 * tree_id + 2, m, tree_r, i, new_val);
+        }
+        self.__pull(tree_id);
     }
 
     /// Friendly interface of `__set` with `i` assert
@@ -164,7 +512,152 @@ impl<T: Eq + Copy> SegmentTree<T> {
     /// * `i` - index of element in initial array
     /// * `new_val` - new value of `i`-th element
     pub fn set(&mut self, i: usize, new_val: T) {
-        assert!(i <= self.n); // Asserts `i` bounds
+        assert!(i < self.n); // Asserts `i` bounds
         self.__set(0, 0, self.n, i, new_val);
     }
+
+    /// Applies `update` to every element of `v[query_l..query_r]`
+    ///
+    /// # Arguments:
+    ///
+    /// * `tree_id` - current node index
+    /// * `tree_l` - left bound of node in array
+    /// * `tree_r` - right bound of node in array (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    /// * `update` - update to apply
+    fn __update(
+        &mut self,
+        tree_id: usize,
+        tree_l: usize,
+        tree_r: usize,
+        query_l: usize,
+        query_r: usize,
+        update: U,
+    ) {
+        if query_l <= tree_l && tree_r <= query_r {
+            // tree_l..tree_r completely lies in query_l..query_r, so update is stored here
+            self.__apply(tree_id, tree_r - tree_l, &update);
+            return;
+        } else if tree_r <= query_l || query_r <= tree_l {
+            // tree_l..tree_r doesn't intersect with query_l..query_r
+            return;
+        }
+
+        self.__push(tree_id, tree_l, tree_r);
+        let m: usize = (tree_l + tree_r) / 2;
+        self.__update(2 * tree_id + 1, tree_l, m, query_l, query_r, update.clone());
+        self.__update(2 * tree_id + 2, m, tree_r, query_l, query_r, update);
+        self.__pull(tree_id);
+    }
+
+    /// Friendly interface of `__update` with query bounds assert
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    /// * `update` - update, which will be applied to every element of `v[query_l..query_r]`
+    pub fn update(&mut self, query_l: usize, query_r: usize, update: U) {
+        // Assert query bounds
+        assert!(query_l < query_r && query_r <= self.n);
+        self.__update(0, 0, self.n, query_l, query_r, update);
+    }
+}
+
+/// Non-recursive bottom-up segment tree, that allows to efficiently compute associative function
+/// on a segment. It has the same `get`/`set` contract as `SegmentTree`, but uses only `2 * N`
+/// nodes and doesn't require `N` to be a power of two.
+///
+/// Described [here](https://codeforces.com/blog/entry/18051).
+///
+/// Memory complexity - O(N), exactly `2 * N` nodes
+///
+/// Time complexity:
+/// * `get` - O(logN)
+/// * `set` - O(logN)
+pub struct BottomUpSegmentTree<T: Clone, M: Monoid<T>> {
+    /// `tree` is a vector containing each node's value. Leaves are `tree[n..2 * n]` and children
+    /// of `tree[i]` are `tree[2 * i]` and `tree[2 * i + 1]`
+    tree: Vec<T>,
+    /// `n` is initial vector's size
+    n: usize,
+    /// `monoid` provides an identity element and an associative combination function, such as
+    /// addition or gcd
+    monoid: M,
+}
+
+/// Bottom-up segment tree generic implementation.
+///
+/// Type `T` must support `Clone` for moving elements from vector tree and creating initial tree.
+impl<T: Clone, M: Monoid<T>> BottomUpSegmentTree<T, M> {
+    /// Constructs and returns bottom-up segment tree based on given vector and monoid
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - An initial vector
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Gcd`
+    pub fn new(v: &[T], monoid: M) -> BottomUpSegmentTree<T, M> {
+        let n: usize = v.len();
+        // Internal nodes are filled with identities, leaves are copied from `v`
+        let mut tree: Vec<T> = vec![monoid.identity(); n];
+        tree.extend_from_slice(v);
+
+        // Build internal nodes from the last one to the root
+        for i in (1..n).rev() {
+            tree[i] = monoid.combine(&tree[2 * i], &tree[2 * i + 1]);
+        }
+
+        return BottomUpSegmentTree { tree, n, monoid };
+    }
+
+    /// Returns `combine` result for `v[query_l..query_r]` query
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, query_l: usize, query_r: usize) -> T {
+        // Assert query bounds
+        assert!(query_l < query_r && query_r <= self.n);
+
+        // Results of left and right borders are kept separately, so the order of combination is
+        // preserved for non-commutative monoids
+        let mut l_result: T = self.monoid.identity();
+        let mut r_result: T = self.monoid.identity();
+        let (mut l, mut r) = (query_l + self.n, query_r + self.n);
+        while l < r {
+            if l % 2 == 1 {
+                // `l` is a right child, so its parent covers elements out of query
+                l_result = self.monoid.combine(&l_result, &self.tree[l]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                // `r - 1` is a left child, so its parent covers elements out of query
+                r -= 1;
+                r_result = self.monoid.combine(&self.tree[r], &r_result);
+            }
+            l /= 2;
+            r /= 2;
+        }
+
+        return self.monoid.combine(&l_result, &r_result);
+    }
+
+    /// Sets `i`-th element of segment tree to `new_val` and updates tree
+    ///
+    /// # Arguments:
+    /// * `i` - index of element in initial array
+    /// * `new_val` - new value of `i`-th element
+    pub fn set(&mut self, i: usize, new_val: T) {
+        assert!(i < self.n); // Asserts `i` bounds
+
+        let mut tree_id: usize = i + self.n;
+        self.tree[tree_id] = new_val;
+        // Update every ancestor of the leaf
+        while tree_id > 1 {
+            tree_id /= 2;
+            self.tree[tree_id] = self
+                .monoid
+                .combine(&self.tree[2 * tree_id], &self.tree[2 * tree_id + 1]);
+        }
+    }
 }
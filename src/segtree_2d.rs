@@ -0,0 +1,320 @@
+use crate::algebra::Matrix;
+use crate::monoid::Monoid;
+
+/// Two-dimensional segment tree (segment tree of segment trees), that allows to efficiently
+/// compute associative and commutative function on a rectangle.
+///
+/// Every node of the tree over rows contains a segment tree over columns, which is built on
+/// combined rows of the node.
+///
+/// Described [here](https://cp-algorithms.com/data_structures/segment_tree.html#generalization-to-higher-dimensions).
+///
+/// Memory complexity - O(NM), where N is number of rows and M is number of columns
+///
+/// Time complexity:
+/// * `get` - O(logN * logM)
+/// * `set` - O(logN * logM)
+pub struct SegmentTree2D<T: Clone, M: Monoid<T>> {
+    /// `tree` is a vector of column trees, `tree[0]` is a column tree of the root node
+    tree: Vec<Vec<T>>,
+    /// `rows` is initial matrix's number of rows
+    rows: usize,
+    /// `cols` is initial matrix's number of columns
+    cols: usize,
+    /// `monoid` provides an identity element and an associative and commutative combination
+    /// function, such as addition or minimum
+    monoid: M,
+}
+
+/// Two-dimensional segment tree generic implementation.
+///
+/// Elements of a rectangle are combined strip by strip, so `combine` must be commutative to get
+/// the same result as row-major order.
+impl<T: Clone, M: Monoid<T>> SegmentTree2D<T, M> {
+    /// Constructs and returns 2D segment tree based on given rectangular vector of rows and
+    /// monoid
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - An initial vector of rows, all rows must have the same non-zero length
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Min`
+    pub fn new(v: &[Vec<T>], monoid: M) -> SegmentTree2D<T, M> {
+        assert!(!v.is_empty() && !v[0].is_empty());
+        let (rows, cols) = (v.len(), v[0].len());
+        assert!(v.iter().all(|row| row.len() == cols));
+
+        let mut seg_tree: SegmentTree2D<T, M> = SegmentTree2D {
+            tree: vec![vec![monoid.identity(); 4 * cols]; 4 * rows],
+            rows,
+            cols,
+            monoid,
+        };
+
+        // Building segment tree
+        seg_tree.__build_rows(v, 0, 0, rows);
+
+        return seg_tree;
+    }
+
+    /// Constructs and returns 2D segment tree based on given matrix and monoid
+    ///
+    /// # Arguments:
+    ///
+    /// * `matrix` - An initial non-empty matrix
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Min`
+    pub fn from_matrix(matrix: &Matrix<T>, monoid: M) -> SegmentTree2D<T, M> {
+        let (rows, _) = matrix.shape();
+        let v: Vec<Vec<T>> = (0..rows).map(|r| matrix[r].to_vec()).collect();
+        return SegmentTree2D::new(&v, monoid);
+    }
+
+    /// Returns number of rows and number of columns
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.cols);
+    }
+
+    /// Builds column tree of `row_id` node and column trees of its descendants
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - initial vector of rows
+    /// * `row_id` - current row node index
+    /// * `row_l` - top bound of row node
+    /// * `row_r` - bottom bound of row node (non-inclusive)
+    fn __build_rows(&mut self, v: &[Vec<T>], row_id: usize, row_l: usize, row_r: usize) {
+        if row_r - row_l > 1 {
+            let m: usize = (row_l + row_r) / 2;
+            self.__build_rows(v, 2 * row_id + 1, row_l, m);
+            self.__build_rows(v, 2 * row_id + 2, m, row_r);
+        }
+        self.__build_cols(v, row_id, row_l, row_r, 0, 0, self.cols);
+    }
+
+    /// Builds column tree of `row_id` node, column trees of its children must be already built
+    ///
+    /// # Arguments:
+    ///
+    /// * `v` - initial vector of rows
+    /// * `row_id` - current row node index
+    /// * `row_l` - top bound of row node
+    /// * `row_r` - bottom bound of row node (non-inclusive)
+    /// * `col_id` - current column node index
+    /// * `col_l` - left bound of column node
+    /// * `col_r` - right bound of column node (non-inclusive)
+    #[allow(clippy::too_many_arguments)]
+    fn __build_cols(
+        &mut self,
+        v: &[Vec<T>],
+        row_id: usize,
+        row_l: usize,
+        row_r: usize,
+        col_id: usize,
+        col_l: usize,
+        col_r: usize,
+    ) {
+        if col_r - col_l > 1 {
+            let m: usize = (col_l + col_r) / 2;
+            self.__build_cols(v, row_id, row_l, row_r, 2 * col_id + 1, col_l, m);
+            self.__build_cols(v, row_id, row_l, row_r, 2 * col_id + 2, m, col_r);
+            self.__pull_col(row_id, col_id);
+        } else if row_r - row_l > 1 {
+            // Single column of several rows is combined from row children
+            self.__pull_row(row_id, col_id);
+        } else {
+            // Single element
+            self.tree[row_id][col_id] = v[row_l][col_l].clone();
+        }
+    }
+
+    /// Recomputes node's value from children in column tree
+    fn __pull_col(&mut self, row_id: usize, col_id: usize) {
+        let row: &Vec<T> = &self.tree[row_id];
+        self.tree[row_id][col_id] = self
+            .monoid
+            .combine(&row[2 * col_id + 1], &row[2 * col_id + 2]);
+    }
+
+    /// Recomputes node's value from the same nodes of row children's column trees
+    fn __pull_row(&mut self, row_id: usize, col_id: usize) {
+        self.tree[row_id][col_id] = self.monoid.combine(
+            &self.tree[2 * row_id + 1][col_id],
+            &self.tree[2 * row_id + 2][col_id],
+        );
+    }
+
+    /// Returns `combine` result for columns `query_l..query_r` of `row_id` node
+    ///
+    /// # Arguments:
+    ///
+    /// * `row_id` - current row node index
+    /// * `col_id` - current column node index
+    /// * `col_l` - left bound of column node
+    /// * `col_r` - right bound of column node (non-inclusive)
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    fn __get_cols(
+        &self,
+        row_id: usize,
+        col_id: usize,
+        col_l: usize,
+        col_r: usize,
+        query_l: usize,
+        query_r: usize,
+    ) -> T {
+        if query_l <= col_l && col_r <= query_r {
+            // col_l..col_r completely lies in query_l..query_r
+            return self.tree[row_id][col_id].clone();
+        } else if col_r <= query_l || query_r <= col_l {
+            // col_l..col_r doesn't intersect with query_l..query_r
+            return self.monoid.identity();
+        }
+
+        let m: usize = (col_l + col_r) / 2;
+        let l_child: T = self.__get_cols(row_id, 2 * col_id + 1, col_l, m, query_l, query_r);
+        let r_child: T = self.__get_cols(row_id, 2 * col_id + 2, m, col_r, query_l, query_r);
+        return self.monoid.combine(&l_child, &r_child);
+    }
+
+    /// Returns `combine` result for rectangle `query_top..query_bottom` x `query_l..query_r`
+    ///
+    /// # Arguments:
+    ///
+    /// * `row_id` - current row node index
+    /// * `row_l` - top bound of row node
+    /// * `row_r` - bottom bound of row node (non-inclusive)
+    /// * `query_top`, `query_bottom` - row bounds of query (bottom is non-inclusive)
+    /// * `query_l`, `query_r` - column bounds of query (right is non-inclusive)
+    #[allow(clippy::too_many_arguments)]
+    fn __get_rows(
+        &self,
+        row_id: usize,
+        row_l: usize,
+        row_r: usize,
+        query_top: usize,
+        query_bottom: usize,
+        query_l: usize,
+        query_r: usize,
+    ) -> T {
+        if query_top <= row_l && row_r <= query_bottom {
+            // row_l..row_r completely lies in query, ask column tree of the node
+            return self.__get_cols(row_id, 0, 0, self.cols, query_l, query_r);
+        } else if row_r <= query_top || query_bottom <= row_l {
+            // row_l..row_r doesn't intersect with query
+            return self.monoid.identity();
+        }
+
+        let m: usize = (row_l + row_r) / 2;
+        let top: T = self.__get_rows(
+            2 * row_id + 1,
+            row_l,
+            m,
+            query_top,
+            query_bottom,
+            query_l,
+            query_r,
+        );
+        let bottom: T = self.__get_rows(
+            2 * row_id + 2,
+            m,
+            row_r,
+            query_top,
+            query_bottom,
+            query_l,
+            query_r,
+        );
+        return self.monoid.combine(&top, &bottom);
+    }
+
+    /// Returns `combine` result for rectangle with top left corner `(r1, c1)` and bottom right
+    /// corner `(r2, c2)` (non-inclusive), i.e. for rows `r1..r2` and columns `c1..c2`
+    ///
+    /// # Arguments:
+    /// * `(r1, c1)` - top left corner of query
+    /// * `(r2, c2)` - bottom right corner of query (non-inclusive)
+    pub fn get(&self, (r1, c1): (usize, usize), (r2, c2): (usize, usize)) -> T {
+        // Assert query bounds
+        assert!(r1 < r2 && r2 <= self.rows);
+        assert!(c1 < c2 && c2 <= self.cols);
+        return self.__get_rows(0, 0, self.rows, r1, r2, c1, c2);
+    }
+
+    /// Updates column tree of `row_id` node after `(r, c)` element is changed
+    ///
+    /// # Arguments:
+    ///
+    /// * `row_id` - current row node index
+    /// * `row_l` - top bound of row node
+    /// * `row_r` - bottom bound of row node (non-inclusive)
+    /// * `col_id` - current column node index
+    /// * `col_l` - left bound of column node
+    /// * `col_r` - right bound of column node (non-inclusive)
+    /// * `c` - column of changed element
+    /// * `new_val` - new value of element, used only in leaf of leaf row
+    #[allow(clippy::too_many_arguments)]
+    fn __set_cols(
+        &mut self,
+        row_id: usize,
+        row_l: usize,
+        row_r: usize,
+        col_id: usize,
+        col_l: usize,
+        col_r: usize,
+        c: usize,
+        new_val: &T,
+    ) {
+        if col_r - col_l > 1 {
+            let m: usize = (col_l + col_r) / 2;
+            if c < m {
+                self.__set_cols(row_id, row_l, row_r, 2 * col_id + 1, col_l, m, c, new_val);
+            } else {
+                self.__set_cols(row_id, row_l, row_r, 2 * col_id + 2, m, col_r, c, new_val);
+            }
+            self.__pull_col(row_id, col_id);
+        } else if row_r - row_l > 1 {
+            self.__pull_row(row_id, col_id);
+        } else {
+            self.tree[row_id][col_id] = new_val.clone();
+        }
+    }
+
+    /// Updates column trees of `row_id` node and its descendants after `(r, c)` element is
+    /// changed
+    ///
+    /// # Arguments:
+    ///
+    /// * `row_id` - current row node index
+    /// * `row_l` - top bound of row node
+    /// * `row_r` - bottom bound of row node (non-inclusive)
+    /// * `r`, `c` - row and column of changed element
+    /// * `new_val` - new value of element
+    fn __set_rows(
+        &mut self,
+        row_id: usize,
+        row_l: usize,
+        row_r: usize,
+        r: usize,
+        c: usize,
+        new_val: &T,
+    ) {
+        if row_r - row_l > 1 {
+            let m: usize = (row_l + row_r) / 2;
+            if r < m {
+                self.__set_rows(2 * row_id + 1, row_l, m, r, c, new_val);
+            } else {
+                self.__set_rows(2 * row_id + 2, m, row_r, r, c, new_val);
+            }
+        }
+        self.__set_cols(row_id, row_l, row_r, 0, 0, self.cols, c, new_val);
+    }
+
+    /// Sets element in row `r` and column `c` to `new_val` and updates tree
+    ///
+    /// # Arguments:
+    /// * `r` - row of element
+    /// * `c` - column of element
+    /// * `new_val` - new value of element
+    pub fn set(&mut self, r: usize, c: usize, new_val: T) {
+        assert!(r < self.rows && c < self.cols); // Asserts `r` and `c` bounds
+        self.__set_rows(0, 0, self.rows, r, c, &new_val);
+    }
+}
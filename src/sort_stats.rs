@@ -0,0 +1,244 @@
+use crate::sortings::*;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+/// Numbers of operations, made by a sorting function
+///
+/// # Fields:
+/// * `comparisons` - number of comparisons of elements
+/// * `swaps` - number of swaps of two different elements, reported by `StatsCollector::observer`
+/// * `moves` - number of element moves: copies (clones), for example, to a merge buffer, and two
+///   moves for every swap
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: u64,
+    pub swaps: u64,
+    pub moves: u64,
+}
+
+/// Collector of `SortStats`. Elements are wrapped into `Counted`, which counts comparisons and
+/// clones, and swaps are counted by `observer`.
+///
+/// Rust moves elements by copying their bytes (for example, in `slice::swap`), which can't be
+/// seen by `Counted`, so swaps are counted only when a sort is run with `*_with_observer` and
+/// `observer`. Every comparison sort, that swaps elements, has such variant, merge sorts make
+/// no swaps.
+///
+/// ```
+/// use rust_aads::sort_stats::StatsCollector;
+/// use rust_aads::sortings::{bubble_sort_with_observer, heap_sort_with_observer, merge_sort};
+///
+/// let collector = StatsCollector::new();
+/// let mut v = collector.wrap(&[3, 1, 2]);
+/// bubble_sort_with_observer(&mut v, collector.observer());
+/// assert_eq!(collector.stats().swaps, 2);
+///
+/// collector.reset();
+/// let mut v = collector.wrap(&[3, 1, 2]);
+/// heap_sort_with_observer(&mut v, collector.observer());
+/// assert_eq!(collector.stats().swaps, 2);
+///
+/// collector.reset();
+/// let mut v = collector.wrap(&[3, 1, 2]);
+/// merge_sort(&mut v);
+/// assert_eq!(collector.stats().swaps, 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct StatsCollector {
+    comparisons: Cell<u64>,
+    swaps: Cell<u64>,
+    clones: Cell<u64>,
+}
+
+impl StatsCollector {
+    /// Constructs and returns collector with zero counters
+    pub fn new() -> StatsCollector {
+        return StatsCollector::default();
+    }
+
+    /// Returns counted numbers of operations
+    pub fn stats(&self) -> SortStats {
+        return SortStats {
+            comparisons: self.comparisons.get(),
+            swaps: self.swaps.get(),
+            moves: self.clones.get() + 2 * self.swaps.get(),
+        };
+    }
+
+    /// Resets all counters
+    pub fn reset(&self) {
+        self.comparisons.set(0);
+        self.swaps.set(0);
+        self.clones.set(0);
+    }
+
+    /// Returns copy of `v` with elements wrapped into `Counted`, which report to this collector.
+    /// Copying itself is not counted
+    ///
+    /// # Arguments:
+    /// * `v` - elements to wrap
+    pub fn wrap<T: Clone>(&self, v: &[T]) -> Vec<Counted<'_, T>> {
+        return v
+            .iter()
+            .map(|value| Counted {
+                value: value.clone(),
+                collector: self,
+            })
+            .collect();
+    }
+
+    /// Returns observer for `*_with_observer` sorts, which counts swaps of two different
+    /// elements. Comparisons are ignored, because they are counted by `Counted` elements
+    pub fn observer(&self) -> impl FnMut(SortEvent) + '_ {
+        return move |event: SortEvent| {
+            if let SortEvent::Swap(i, j) = event {
+                if i != j {
+                    self.swaps.set(self.swaps.get() + 1);
+                }
+            }
+        };
+    }
+}
+
+/// Element wrapper, that counts its comparisons and clones in a `StatsCollector`. Can be sorted
+/// by any function of `sortings`, that sorts `Ord` elements, and by key extraction functions
+/// through `value`.
+#[derive(Debug)]
+pub struct Counted<'a, T> {
+    value: T,
+    collector: &'a StatsCollector,
+}
+
+impl<T> Counted<'_, T> {
+    /// Returns wrapped value
+    pub fn value(&self) -> &T {
+        return &self.value;
+    }
+
+    /// Returns wrapped value and consumes wrapper
+    pub fn into_inner(self) -> T {
+        return self.value;
+    }
+}
+
+impl<T: Clone> Clone for Counted<'_, T> {
+    fn clone(&self) -> Self {
+        self.collector.clones.set(self.collector.clones.get() + 1);
+        return Counted {
+            value: self.value.clone(),
+            collector: self.collector,
+        };
+    }
+}
+
+impl<T: Ord> Ord for Counted<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let comparisons: &Cell<u64> = &self.collector.comparisons;
+        comparisons.set(comparisons.get() + 1);
+        return self.value.cmp(&other.value);
+    }
+}
+
+impl<T: Ord> PartialOrd for Counted<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl<T: Ord> PartialEq for Counted<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.cmp(other) == Ordering::Equal;
+    }
+}
+
+impl<T: Ord> Eq for Counted<'_, T> {}
+
+/// Sorting function for `compare_sorts`, receives wrapped elements and their collector
+type CountedSortFn<T> = for<'a> fn(&mut [Counted<'a, T>], &'a StatsCollector);
+
+/// Runs every comparison sort of `sortings` on a copy of `v` and returns their names and
+/// `SortStats`. Quicksort with random pivot is not included, because its results are not
+/// reproducible.
+///
+/// **NOTE:** O(N^2) sorts are run too, so `v` shouldn't be large.
+///
+/// # Arguments:
+/// * `v` - dataset, that is sorted by every function
+pub fn compare_sorts<T: Ord + Clone>(v: &[T]) -> Vec<(&'static str, SortStats)> {
+    let sorts: [(&'static str, CountedSortFn<T>); 17] = [
+        ("bubble_sort", |v, c| {
+            bubble_sort_with_observer(v, c.observer())
+        }),
+        ("selection_sort", |v, c| {
+            selection_sort_with_observer(v, c.observer())
+        }),
+        ("insertion_sort", |v, c| {
+            insertion_sort_with_observer(v, c.observer())
+        }),
+        ("shell_sort (Ciura)", |v, c| {
+            shell_sort_with_observer(v, GapSequence::Ciura, c.observer())
+        }),
+        ("shell_sort (Knuth)", |v, c| {
+            shell_sort_with_observer(v, GapSequence::Knuth, c.observer())
+        }),
+        ("comb_sort", |v, c| comb_sort_with_observer(v, c.observer())),
+        ("gnome_sort", |v, c| {
+            gnome_sort_with_observer(v, c.observer())
+        }),
+        ("cocktail_shaker_sort", |v, c| {
+            cocktail_shaker_sort_with_observer(v, c.observer())
+        }),
+        ("odd_even_sort", |v, c| {
+            odd_even_sort_with_observer(v, c.observer())
+        }),
+        ("merge_sort", |v, _| merge_sort(v)),
+        ("bottom_up_merge_sort", |v, _| bottom_up_merge_sort(v)),
+        ("quick_sort (first)", |v, c| {
+            quick_sort_with_observer(v, PivotStrategy::First, c.observer())
+        }),
+        ("quick_sort (median of 3)", |v, c| {
+            quick_sort_with_observer(v, PivotStrategy::MedianOfThree, c.observer())
+        }),
+        ("quick_sort (ninther)", |v, c| {
+            quick_sort_with_observer(v, PivotStrategy::Ninther, c.observer())
+        }),
+        ("three_way_quick_sort", |v, c| {
+            three_way_quick_sort_with_observer(v, PivotStrategy::Ninther, c.observer())
+        }),
+        ("intro_sort", |v, c| {
+            intro_sort_with_observer(v, c.observer())
+        }),
+        ("heap_sort", |v, c| heap_sort_with_observer(v, c.observer())),
+    ];
+
+    let mut results: Vec<(&'static str, SortStats)> = Vec::with_capacity(sorts.len());
+    for (name, sort) in sorts {
+        let collector: StatsCollector = StatsCollector::new();
+        let mut wrapped: Vec<Counted<T>> = collector.wrap(v);
+        sort(&mut wrapped, &collector);
+        results.push((name, collector.stats()));
+    }
+    return results;
+}
+
+/// Runs every comparison sort of `sortings` on a copy of `v` (see `compare_sorts`) and returns a
+/// table with numbers of comparisons, swaps and moves.
+///
+/// # Arguments:
+/// * `v` - dataset, that is sorted by every function
+pub fn sort_report<T: Ord + Clone>(v: &[T]) -> String {
+    let mut report: String = format!(
+        "{:<26}{:>14}{:>14}{:>14}\n",
+        "sort", "comparisons", "swaps", "moves"
+    );
+    for (name, stats) in compare_sorts(v) {
+        writeln!(
+            report,
+            "{:<26}{:>14}{:>14}{:>14}",
+            name, stats.comparisons, stats.swaps, stats.moves
+        )
+        .unwrap();
+    }
+    return report;
+}
@@ -1,151 +1,1633 @@
-/// Sorts vector using [bubble sort algorithm](https://en.wikipedia.org/wiki/Bubble_sort), time
-/// complexity is O(N^2)
+use rand::Rng;
+use std::cmp::Ordering;
+
+/// Operation of a sorting algorithm, which is passed to an observer (for example, to animate or
+/// count operations)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortEvent {
+    /// Elements at two indices are compared
+    Compare(usize, usize),
+    /// Elements at two indices are swapped
+    Swap(usize, usize),
+}
+
+/// Slice with a comparator, that reports every comparison and swap to an observer
 ///
-/// # Arguments:
-/// * v - mutable vector, that will be sorted
-pub fn bubble_sort<T: Ord + Copy>(v: &mut Vec<T>) {
-    let n: usize = v.len();
+/// # Fields:
+/// * `v` - slice, that is sorted
+/// * `cmp` - comparator, returns ordering of two elements
+/// * `observer` - callback, which receives every `SortEvent`
+struct ObservedSlice<'a, T, F, O> {
+    v: &'a mut [T],
+    cmp: F,
+    observer: O,
+}
+
+impl<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)> ObservedSlice<'_, T, F, O> {
+    /// Returns number of elements
+    fn len(&self) -> usize {
+        return self.v.len();
+    }
+
+    /// Compares `v[i]` with `v[j]` and returns their ordering
+    fn compare(&mut self, i: usize, j: usize) -> Ordering {
+        (self.observer)(SortEvent::Compare(i, j));
+        return (self.cmp)(&self.v[i], &self.v[j]);
+    }
+
+    /// Compares `v[i]` with `v[j]` and returns `true` if `v[i]` is greater
+    fn greater(&mut self, i: usize, j: usize) -> bool {
+        return self.compare(i, j) == Ordering::Greater;
+    }
+
+    /// Swaps `v[i]` and `v[j]`
+    fn swap(&mut self, i: usize, j: usize) {
+        (self.observer)(SortEvent::Swap(i, j));
+        self.v.swap(i, j);
+    }
+}
+
+/// Declares public sorting functions for a sort, which is implemented as
+/// `fn(&mut ObservedSlice, ...args)`: plain (for `Ord` elements), `_by` (with comparator),
+/// `_by_key` (with key extraction function) and `_with_observer` (with `SortEvent` callback)
+macro_rules! observed_sort {
+    (
+        $(#[$doc:meta])*
+        $sort:ident, $sort_by:ident, $sort_by_key:ident, $sort_with_observer:ident,
+        $implementation:ident $(, $arg:ident: $arg_ty:ty)*
+    ) => {
+        $(#[$doc])*
+        ///
+        /// # Arguments:
+        /// * v - mutable slice, that will be sorted
+        $(#[doc = concat!("* ", stringify!($arg), " - see `", stringify!($arg_ty), "`")])*
+        pub fn $sort<T: Ord>(v: &mut [T] $(, $arg: $arg_ty)*) {
+            $sort_by(v $(, $arg)*, T::cmp);
+        }
+
+        $(#[$doc])*
+        ///
+        /// Elements are compared with comparator.
+        ///
+        /// # Arguments:
+        /// * v - mutable slice, that will be sorted
+        $(#[doc = concat!("* ", stringify!($arg), " - see `", stringify!($arg_ty), "`")])*
+        /// * cmp - comparator, returns ordering of two elements
+        pub fn $sort_by<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T] $(, $arg: $arg_ty)*, cmp: F) {
+            let observer = |_: SortEvent| {};
+            $implementation(&mut ObservedSlice { v, cmp, observer } $(, $arg)*);
+        }
+
+        $(#[$doc])*
+        ///
+        /// Elements are compared by keys, extracted from them.
+        ///
+        /// # Arguments:
+        /// * v - mutable slice, that will be sorted
+        $(#[doc = concat!("* ", stringify!($arg), " - see `", stringify!($arg_ty), "`")])*
+        /// * f - key extraction function
+        pub fn $sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(
+            v: &mut [T]
+            $(, $arg: $arg_ty)*,
+            mut f: F,
+        ) {
+            $sort_by(v $(, $arg)*, |a, b| f(a).cmp(&f(b)));
+        }
+
+        $(#[$doc])*
+        ///
+        /// Every comparison and swap is reported to `observer` before it is made.
+        ///
+        /// # Arguments:
+        /// * v - mutable slice, that will be sorted
+        $(#[doc = concat!("* ", stringify!($arg), " - see `", stringify!($arg_ty), "`")])*
+        /// * observer - callback, which receives every `SortEvent`
+        pub fn $sort_with_observer<T: Ord, O: FnMut(SortEvent)>(
+            v: &mut [T]
+            $(, $arg: $arg_ty)*,
+            observer: O,
+        ) {
+            $implementation(&mut ObservedSlice { v, cmp: T::cmp, observer } $(, $arg)*);
+        }
+    };
+}
+
+observed_sort!(
+    /// Sorts slice using [bubble sort algorithm](https://en.wikipedia.org/wiki/Bubble_sort), time
+    /// complexity is O(N^2). Sort is stable.
+    bubble_sort, bubble_sort_by, bubble_sort_by_key, bubble_sort_with_observer, __bubble_sort
+);
+
+/// Bubble sort: adjacent elements in wrong order are swapped, so after `i`-th pass the largest
+/// `i` elements are at the end
+fn __bubble_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
     for i in 0..n {
         for j in 0..n - i - 1 {
-            if v[j] > v[j + 1] {
-                v.swap(j, j + 1);
+            if s.greater(j, j + 1) {
+                s.swap(j, j + 1);
             }
         }
     }
 }
 
-/// Sorts vector using [selection sort algorithm](https://en.wikipedia.org/wiki/Selection_sort),
-/// time complexity is O(N^2)
-///
-/// # Arguments:
-/// * v - mutable vector, that will be sorted
-pub fn selection_sort<T: Copy + Ord>(v: &mut Vec<T>) {
-    let n: usize = v.len();
-    for i in 0..n - 1 {
+observed_sort!(
+    /// Sorts slice using [selection sort algorithm](https://en.wikipedia.org/wiki/Selection_sort),
+    /// time complexity is O(N^2). Sort is not stable.
+    selection_sort, selection_sort_by, selection_sort_by_key, selection_sort_with_observer,
+    __selection_sort
+);
+
+/// Selection sort: minimum of unsorted part is swapped with its first element
+fn __selection_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
+    for i in 0..n {
         // Find first_idx (minimum) and place it into beginning of v
         let mut first_idx = i;
         for j in i + 1..n {
-            if v[first_idx] > v[j] {
+            if s.greater(first_idx, j) {
                 first_idx = j;
             }
         }
 
-        v.swap(i, first_idx);
+        if first_idx != i {
+            s.swap(i, first_idx);
+        }
     }
 }
 
-/// Sorts vector using [insertion sort algorithm](https://en.wikipedia.org/wiki/Insertion_sort),
-/// time complexity is O(N^2)
+observed_sort!(
+    /// Sorts slice using [insertion sort algorithm](https://en.wikipedia.org/wiki/Insertion_sort),
+    /// time complexity is O(N^2). Sort is stable.
+    insertion_sort, insertion_sort_by, insertion_sort_by_key, insertion_sort_with_observer,
+    __observed_insertion_sort
+);
+
+/// Insertion sort: every element is moved left, while previous element is greater
+fn __observed_insertion_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
+    __insertion_sort_range(s, 0, n);
+}
+
+/// Sorts `v[lo..hi]` with insertion sort, used for small partitions of other sorts
+fn __insertion_sort_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    lo: usize,
+    hi: usize,
+) {
+    for i in lo + 1..hi {
+        let mut j = i;
+        while j > lo && s.greater(j - 1, j) {
+            s.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// Sorts slice with insertion sort, used for small partitions of other sorts
 ///
 /// # Arguments:
-/// * v - mutable vector, that will be sorted
-pub fn insertion_sort<T: Copy + Ord>(v: &mut Vec<T>) {
+/// * v - mutable slice, that will be sorted
+/// * cmp - comparator, returns ordering of two elements
+fn __insertion_sort<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], cmp: &mut F) {
+    let observer = |_: SortEvent| {};
+    __observed_insertion_sort(&mut ObservedSlice { v, cmp, observer });
+}
+
+/// Gap sequence of Shell sort
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GapSequence {
+    /// [Ciura's sequence](https://oeis.org/A102549) `1, 4, 10, 23, 57, 132, 301, 701`, extended
+    /// by multiplying by 2.25. Best known sequence in practice
+    Ciura,
+    /// [Knuth's sequence](https://oeis.org/A003462) `(3^k - 1) / 2`, which is not greater than
+    /// `N / 3`, time complexity is O(N^(3/2))
+    Knuth,
+}
+
+impl GapSequence {
+    /// Returns gaps for slice of length `n` in decreasing order, the last gap is `1`
+    fn gaps(&self, n: usize) -> Vec<usize> {
+        let mut gaps: Vec<usize> = vec![1];
+        match self {
+            GapSequence::Ciura => {
+                for gap in [4, 10, 23, 57, 132, 301, 701] {
+                    if gap >= n {
+                        break;
+                    }
+                    gaps.push(gap);
+                }
+                // Long slices need gaps after 701
+                while gaps.len() >= 8 && gaps[gaps.len() - 1] * 9 / 4 < n {
+                    gaps.push(gaps[gaps.len() - 1] * 9 / 4);
+                }
+            }
+            GapSequence::Knuth => {
+                let mut gap: usize = 4;
+                while gap <= n / 3 {
+                    gaps.push(gap);
+                    gap = 3 * gap + 1;
+                }
+            }
+        }
+        gaps.reverse();
+        return gaps;
+    }
+}
+
+observed_sort!(
+    /// Sorts slice using [Shell sort algorithm](https://en.wikipedia.org/wiki/Shellsort):
+    /// insertion sort of elements, that are `gap` apart, for decreasing gaps. Time complexity
+    /// depends on gap sequence, it is about O(N^(4/3)) for Ciura's one. Sort is not stable.
+    shell_sort, shell_sort_by, shell_sort_by_key, shell_sort_with_observer, __shell_sort,
+    gaps: GapSequence
+);
+
+/// Shell sort: insertion sort with decreasing gaps
+fn __shell_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    gaps: GapSequence,
+) {
+    let n: usize = s.len();
+    for gap in gaps.gaps(n) {
+        for i in gap..n {
+            let mut j: usize = i;
+            while j >= gap && s.greater(j - gap, j) {
+                s.swap(j, j - gap);
+                j -= gap;
+            }
+        }
+    }
+}
+
+observed_sort!(
+    /// Sorts slice using [comb sort algorithm](https://en.wikipedia.org/wiki/Comb_sort): bubble
+    /// sort of elements, that are `gap` apart, where gap shrinks by factor 1.3 until it is `1`.
+    /// Time complexity is O(N^2) in the worst case, but close to O(NlogN) on average. Sort is not
+    /// stable.
+    comb_sort, comb_sort_by, comb_sort_by_key, comb_sort_with_observer, __comb_sort
+);
+
+/// Comb sort: passes with shrinking gap, until a pass with gap `1` makes no swaps
+fn __comb_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
+    let mut gap: usize = n;
+    let mut sorted: bool = false;
+    while !sorted {
+        // Shrink factor is 1.3
+        gap = (gap * 10 / 13).max(1);
+        sorted = gap == 1;
+        for i in 0..n.saturating_sub(gap) {
+            if s.greater(i, i + gap) {
+                s.swap(i, i + gap);
+                sorted = false;
+            }
+        }
+    }
+}
+
+observed_sort!(
+    /// Sorts slice using [gnome sort algorithm](https://en.wikipedia.org/wiki/Gnome_sort): like
+    /// insertion sort, but element is moved back with swaps and then the gnome walks forward again.
+    /// Time complexity is O(N^2). Sort is stable.
+    gnome_sort, gnome_sort_by, gnome_sort_by_key, gnome_sort_with_observer, __gnome_sort
+);
+
+/// Gnome sort: step forward if pair is in order, otherwise swap it and step back
+fn __gnome_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
+    let mut i: usize = 1;
+    while i < n {
+        if i > 0 && s.greater(i - 1, i) {
+            s.swap(i - 1, i);
+            i -= 1;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+observed_sort!(
+    /// Sorts slice using
+    /// [cocktail shaker sort algorithm](https://en.wikipedia.org/wiki/Cocktail_shaker_sort):
+    /// bubble sort, which passes in both directions, so small elements at the end move fast. Time
+    /// complexity is O(N^2). Sort is stable.
+    cocktail_shaker_sort, cocktail_shaker_sort_by, cocktail_shaker_sort_by_key,
+    cocktail_shaker_sort_with_observer, __cocktail_shaker_sort
+);
+
+/// Cocktail shaker sort: forward pass moves maximum of `v[l..r]` to the end, backward pass moves
+/// minimum to the beginning
+fn __cocktail_shaker_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let (mut l, mut r): (usize, usize) = (0, s.len());
+    while r - l > 1 {
+        // Elements after the last swap are already in place
+        let mut last_swap: usize = l;
+        for i in l..r - 1 {
+            if s.greater(i, i + 1) {
+                s.swap(i, i + 1);
+                last_swap = i + 1;
+            }
+        }
+        r = last_swap;
+        if r - l <= 1 {
+            break;
+        }
+
+        let mut first_swap: usize = r;
+        for i in (l + 1..r).rev() {
+            if s.greater(i - 1, i) {
+                s.swap(i - 1, i);
+                first_swap = i - 1;
+            }
+        }
+        l = first_swap;
+    }
+}
+
+observed_sort!(
+    /// Sorts slice using [odd-even sort algorithm](https://en.wikipedia.org/wiki/Odd%E2%80%93even_sort):
+    /// alternating passes compare pairs with odd and even first index, comparisons of a pass are
+    /// independent (so they can be done in parallel). Time complexity is O(N^2). Sort is stable.
+    odd_even_sort, odd_even_sort_by, odd_even_sort_by_key, odd_even_sort_with_observer,
+    __odd_even_sort
+);
+
+/// Odd-even sort: passes over odd and even pairs, until both of them make no swaps
+fn __odd_even_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
+    let mut sorted: bool = false;
+    while !sorted {
+        sorted = true;
+        for start in [1, 0] {
+            for i in (start..n.saturating_sub(1)).step_by(2) {
+                if s.greater(i, i + 1) {
+                    s.swap(i, i + 1);
+                    sorted = false;
+                }
+            }
+        }
+    }
+}
+
+/// Merges sorted `v[..mid]` and `v[mid..]` into sorted `v` and returns number of inversions
+/// between halves (pairs of left element and right element, that are in wrong order).
+///
+/// Left half is moved to `buffer`, so it takes `mid` additional memory. Elements of the left half
+/// are taken first on ties, so merging is stable.
+///
+/// # Arguments:
+/// * `v` - slice, which consists of two sorted halves
+/// * `mid` - start of the right half
+/// * `buffer` - scratch buffer, its contents are overwritten
+/// * `cmp` - comparator, returns ordering of two elements
+fn __merge<T: Clone, F: FnMut(&T, &T) -> Ordering>(
+    v: &mut [T],
+    mid: usize,
+    buffer: &mut Vec<T>,
+    cmp: &mut F,
+) -> u64 {
+    buffer.clear();
+    buffer.extend_from_slice(&v[..mid]);
+
+    let mut inversions: u64 = 0;
+    // i - index in buffer (left half), j - index in right half, k - index of written element
+    let (mut i, mut j, mut k): (usize, usize, usize) = (0, mid, 0);
+    while i < buffer.len() && j < v.len() {
+        if cmp(&v[j], &buffer[i]) == Ordering::Less {
+            // Right element is less than every remaining left element
+            inversions += (buffer.len() - i) as u64;
+            v[k] = v[j].clone();
+            j += 1;
+        } else {
+            v[k] = buffer[i].clone();
+            i += 1;
+        }
+        k += 1;
+    }
+
+    // Remaining right elements are already in place
+    while i < buffer.len() {
+        v[k] = buffer[i].clone();
+        i += 1;
+        k += 1;
+    }
+
+    return inversions;
+}
+
+/// Recursively sorts `v` with top-down merge sort and returns number of inversions in it
+///
+/// # Arguments:
+/// * `v` - slice, that will be sorted
+/// * `buffer` - scratch buffer for merging
+/// * `cmp` - comparator, returns ordering of two elements
+fn __merge_sort<T: Clone, F: FnMut(&T, &T) -> Ordering>(
+    v: &mut [T],
+    buffer: &mut Vec<T>,
+    cmp: &mut F,
+) -> u64 {
     let n: usize = v.len();
-    for i in 1..n {
-        let mut j = i;
-        while j > 0 && v[j - 1] > v[j] {
-            v.swap(j, j - 1);
-            j -= 1;
+    if n <= 1 {
+        return 0;
+    }
+
+    let mid: usize = n / 2;
+    let left_inversions: u64 = __merge_sort(&mut v[..mid], buffer, cmp);
+    let right_inversions: u64 = __merge_sort(&mut v[mid..], buffer, cmp);
+    return left_inversions + right_inversions + __merge(v, mid, buffer, cmp);
+}
+
+/// Sorts `v` with bottom-up merge sort: merges adjacent sorted blocks of length 1, 2, 4 and so
+/// on, no recursion is used
+///
+/// # Arguments:
+/// * `v` - slice, that will be sorted
+/// * `buffer` - scratch buffer for merging
+/// * `cmp` - comparator, returns ordering of two elements
+fn __bottom_up_merge_sort<T: Clone, F: FnMut(&T, &T) -> Ordering>(
+    v: &mut [T],
+    buffer: &mut Vec<T>,
+    cmp: &mut F,
+) {
+    let n: usize = v.len();
+    let mut width: usize = 1;
+    while width < n {
+        for l in (0..n - width).step_by(2 * width) {
+            let r: usize = (l + 2 * width).min(n);
+            __merge(&mut v[l..r], width, buffer, cmp);
         }
+        width *= 2;
     }
 }
 
-/// Sorts a vector od `i32`s using
-/// [counting sort algorithm](https://en.wikipedia.org/wiki/Counting_sort),
-/// time complexity is O(N), where `N` is a length of `[min_element, max_element]`.
+/// Sorts slice using [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort), time
+/// complexity is O(NlogN), additional memory is O(N).
+///
+/// Sort is stable: equal elements keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+pub fn merge_sort<T: Ord + Clone>(v: &mut [T]) {
+    merge_sort_by(v, T::cmp);
+}
+
+/// Sorts slice using [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort) with
+/// comparator, time complexity is O(NlogN), additional memory is O(N).
+///
+/// Sort is stable: elements, that are equal according to `cmp`, keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * cmp - comparator, returns ordering of two elements
+pub fn merge_sort_by<T: Clone, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], mut cmp: F) {
+    let mut buffer: Vec<T> = Vec::with_capacity(v.len() / 2);
+    __merge_sort(v, &mut buffer, &mut cmp);
+}
+
+/// Sorts slice using [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort) by keys,
+/// extracted from elements, time complexity is O(NlogN), additional memory is O(N).
+///
+/// Sort is stable: elements with equal keys keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * f - key extraction function
+pub fn merge_sort_by_key<T: Clone, K: Ord, F: FnMut(&T) -> K>(v: &mut [T], mut f: F) {
+    merge_sort_by(v, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Sorts slice using [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort) with given
+/// scratch buffer, so sorting many slices needs no new allocations once buffer has grown to
+/// half of the largest slice.
+///
+/// Sort is stable: equal elements keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * buffer - scratch buffer, its contents are overwritten
+pub fn merge_sort_with_buffer<T: Ord + Clone>(v: &mut [T], buffer: &mut Vec<T>) {
+    __merge_sort(v, buffer, &mut T::cmp);
+}
+
+/// Sorts slice using bottom-up (non-recursive)
+/// [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort#Bottom-up_implementation),
+/// time complexity is O(NlogN), additional memory is O(N).
 ///
-/// **NOTE:** this is not a generic implementation, it can only be used for `Vec<i32>`. Generic
-/// implementation uses [`Step` trait](https://doc.rust-lang.org/std/iter/trait.Step.html), see
-/// [issue #42168](https://github.com/rust-lang/rust/issues/42168).
+/// Sort is stable: equal elements keep their relative order.
 ///
-/// <details>
-///     <summary>Generic implementation</summary>
-///     ``` use std::hash::Hash;
-/// use std::iter::Step;
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+pub fn bottom_up_merge_sort<T: Ord + Clone>(v: &mut [T]) {
+    bottom_up_merge_sort_by(v, T::cmp);
+}
+
+/// Sorts slice using bottom-up (non-recursive)
+/// [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort#Bottom-up_implementation)
+/// with comparator, time complexity is O(NlogN), additional memory is O(N).
+///
+/// Sort is stable: elements, that are equal according to `cmp`, keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * cmp - comparator, returns ordering of two elements
+pub fn bottom_up_merge_sort_by<T: Clone, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], mut cmp: F) {
+    let mut buffer: Vec<T> = Vec::with_capacity(v.len() / 2);
+    __bottom_up_merge_sort(v, &mut buffer, &mut cmp);
+}
+
+/// Sorts slice using bottom-up (non-recursive)
+/// [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort#Bottom-up_implementation) by
+/// keys, extracted from elements, time complexity is O(NlogN), additional memory is O(N).
+///
+/// Sort is stable: elements with equal keys keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * f - key extraction function
+pub fn bottom_up_merge_sort_by_key<T: Clone, K: Ord, F: FnMut(&T) -> K>(v: &mut [T], mut f: F) {
+    bottom_up_merge_sort_by(v, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Sorts slice using bottom-up (non-recursive)
+/// [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort#Bottom-up_implementation)
+/// with given scratch buffer.
 ///
-/// pub fn counting_sort<T: Copy + Ord + Step + Hash>(v: &mut Vec<T>) {
-///     let n = v.len();
-///     if n == 0 { return; }
+/// Sort is stable: equal elements keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * buffer - scratch buffer, its contents are overwritten
+pub fn bottom_up_merge_sort_with_buffer<T: Ord + Clone>(v: &mut [T], buffer: &mut Vec<T>) {
+    __bottom_up_merge_sort(v, buffer, &mut T::cmp);
+}
+
+/// Sorts slice using [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort) and returns
+/// number of [inversions](https://en.wikipedia.org/wiki/Inversion_(discrete_mathematics)) in it -
+/// pairs `i < j` such `v[i] > v[j]`. Time complexity is O(NlogN).
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+pub fn count_inversions<T: Ord + Clone>(v: &mut [T]) -> u64 {
+    let mut buffer: Vec<T> = Vec::with_capacity(v.len() / 2);
+    return __merge_sort(v, &mut buffer, &mut T::cmp);
+}
+
+/// Strategy of choosing a pivot element in quicksort
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PivotStrategy {
+    /// First element of a partition. Sorted and reversed inputs take O(N^2)
+    First,
+    /// Median of first, middle and last elements of a partition
+    MedianOfThree,
+    /// Uniformly random element of a partition, expected time is O(NlogN) for any input
+    Random,
+    /// Median of three medians of three (Tukey's ninther), falls back to median of three for
+    /// small partitions
+    Ninther,
+}
+
+/// Partitions of this length or shorter are sorted with insertion sort in introsort
+const INSERTION_SORT_THRESHOLD: usize = 16;
+
+/// Partitions shorter than this use median of three instead of ninther
+const NINTHER_THRESHOLD: usize = 128;
+
+/// Returns index of median of `v[a]`, `v[b]` and `v[c]`
+fn __median_of_three<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    (a, b, c): (usize, usize, usize),
+) -> usize {
+    let mut less = |i: usize, j: usize| s.compare(i, j) == Ordering::Less;
+    if less(a, b) {
+        if less(b, c) {
+            return b;
+        } else if less(a, c) {
+            return c;
+        }
+        return a;
+    } else if less(a, c) {
+        return a;
+    } else if less(b, c) {
+        return c;
+    }
+    return b;
+}
+
+/// Returns index of pivot element of non-empty `v[lo..hi]`, chosen with `strategy`
+fn __choose_pivot_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    lo: usize,
+    hi: usize,
+    strategy: PivotStrategy,
+) -> usize {
+    let n: usize = hi - lo;
+    let mid: usize = lo + n / 2;
+    return match strategy {
+        PivotStrategy::First => lo,
+        PivotStrategy::MedianOfThree => __median_of_three(s, (lo, mid, hi - 1)),
+        PivotStrategy::Random => lo + rand::thread_rng().gen_range(0..n),
+        PivotStrategy::Ninther if n < NINTHER_THRESHOLD => __median_of_three(s, (lo, mid, hi - 1)),
+        PivotStrategy::Ninther => {
+            let step: usize = n / 8;
+            let first: usize = __median_of_three(s, (lo, lo + step, lo + 2 * step));
+            let middle: usize = __median_of_three(s, (mid - step, mid, mid + step));
+            let last: usize = __median_of_three(s, (hi - 1 - 2 * step, hi - 1 - step, hi - 1));
+            __median_of_three(s, (first, middle, last))
+        }
+    };
+}
+
+/// Returns index of pivot element of non-empty `v`, chosen with `strategy`
+fn __choose_pivot<T, F: FnMut(&T, &T) -> Ordering>(
+    v: &mut [T],
+    strategy: PivotStrategy,
+    cmp: &mut F,
+) -> usize {
+    let n: usize = v.len();
+    let observer = |_: SortEvent| {};
+    return __choose_pivot_range(&mut ObservedSlice { v, cmp, observer }, 0, n, strategy);
+}
+
+/// Partitions non-empty `v[lo..hi]` around `v[pivot]` with
+/// [Hoare partition scheme](https://en.wikipedia.org/wiki/Quicksort#Hoare_partition_scheme).
+/// Returns final index `m` of pivot, `v[lo..m]` are not greater and `v[m + 1..hi]` are not less
+/// than it. Elements equal to pivot are split between both parts, so duplicates don't degrade it.
+fn __partition_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    lo: usize,
+    hi: usize,
+    pivot: usize,
+) -> usize {
+    // Pivot is kept at index `lo` during partitioning
+    if pivot != lo {
+        s.swap(lo, pivot);
+    }
+    let (mut i, mut j): (usize, usize) = (lo + 1, hi - 1);
+    loop {
+        while i <= j && s.compare(i, lo) == Ordering::Less {
+            i += 1;
+        }
+        while i <= j && s.compare(j, lo) == Ordering::Greater {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        s.swap(i, j);
+        i += 1;
+        j -= 1;
+    }
+
+    // `v[j]` is not greater than pivot (or it is pivot itself)
+    if j != lo {
+        s.swap(lo, j);
+    }
+    return j;
+}
+
+/// Partitions non-empty `v[lo..hi]` around `v[pivot]` into three parts with
+/// [Dutch national flag algorithm](https://en.wikipedia.org/wiki/Dutch_national_flag_problem).
+/// Returns `(lt, gt)`, such `v[lo..lt]` are less than pivot, `v[lt..gt]` are equal to it and
+/// `v[gt..hi]` are greater.
+fn __partition_three_way_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    lo: usize,
+    hi: usize,
+    pivot: usize,
+) -> (usize, usize) {
+    if pivot != lo {
+        s.swap(lo, pivot);
+    }
+    // `v[lt..i]` are equal to pivot, so `v[lt]` is always an element equal to pivot
+    let (mut lt, mut i, mut gt): (usize, usize, usize) = (lo, lo + 1, hi);
+    while i < gt {
+        match s.compare(i, lt) {
+            Ordering::Less => {
+                s.swap(lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                if i != gt {
+                    s.swap(i, gt);
+                }
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    return (lt, gt);
+}
+
+/// Partitions non-empty `v` around `v[pivot]` into three parts, see `__partition_three_way_range`
+fn __partition_three_way<T, F: FnMut(&T, &T) -> Ordering>(
+    v: &mut [T],
+    pivot: usize,
+    cmp: &mut F,
+) -> (usize, usize) {
+    let n: usize = v.len();
+    let observer = |_: SortEvent| {};
+    return __partition_three_way_range(&mut ObservedSlice { v, cmp, observer }, 0, n, pivot);
+}
+
+/// Sorts `v[lo..hi]` with quicksort. Smaller part is sorted recursively and larger one in a
+/// loop, so recursion depth is O(logN) for any input
 ///
-///     use std::collections::HashMap;
-///     let mut counter = HashMap::new();
+/// # Arguments:
+/// * s - observed slice
+/// * lo - left bound of sorted part
+/// * hi - right bound of sorted part (non-inclusive)
+/// * strategy - pivot strategy
+/// * three_way - if `true`, elements equal to pivot are excluded from both parts
+fn __quick_sort_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    mut lo: usize,
+    mut hi: usize,
+    strategy: PivotStrategy,
+    three_way: bool,
+) {
+    while hi - lo > 1 {
+        let pivot: usize = __choose_pivot_range(s, lo, hi, strategy);
+        let (l, r): (usize, usize) = if three_way {
+            __partition_three_way_range(s, lo, hi, pivot)
+        } else {
+            let m: usize = __partition_range(s, lo, hi, pivot);
+            (m, m + 1)
+        };
+
+        if l - lo < hi - r {
+            __quick_sort_range(s, lo, l, strategy, three_way);
+            lo = r;
+        } else {
+            __quick_sort_range(s, r, hi, strategy, three_way);
+            hi = l;
+        }
+    }
+}
+
+observed_sort!(
+    /// Sorts slice using [quicksort algorithm](https://en.wikipedia.org/wiki/Quicksort) with
+    /// Hoare partition scheme. Time complexity is O(NlogN) on average, but O(N^2) in the worst
+    /// case (depends on `pivot` strategy), additional memory is O(logN). Sort is not stable.
+    quick_sort, quick_sort_by, quick_sort_by_key, quick_sort_with_observer,
+    __observed_quick_sort, pivot: PivotStrategy
+);
+
+/// Quicksort with Hoare partition scheme
+fn __observed_quick_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    pivot: PivotStrategy,
+) {
+    let n: usize = s.len();
+    __quick_sort_range(s, 0, n, pivot, false);
+}
+
+observed_sort!(
+    /// Sorts slice using [quicksort algorithm](https://en.wikipedia.org/wiki/Quicksort) with
+    /// [three-way partitioning](https://en.wikipedia.org/wiki/Quicksort#Repeated_elements):
+    /// elements equal to pivot are placed between two parts and are not sorted further, so input
+    /// with many duplicates is sorted faster. Time complexity is O(NlogN) on average, additional
+    /// memory is O(logN). Sort is not stable.
+    three_way_quick_sort, three_way_quick_sort_by, three_way_quick_sort_by_key,
+    three_way_quick_sort_with_observer, __observed_three_way_quick_sort, pivot: PivotStrategy
+);
+
+/// Quicksort with three-way partitioning
+fn __observed_three_way_quick_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    pivot: PivotStrategy,
+) {
+    let n: usize = s.len();
+    __quick_sort_range(s, 0, n, pivot, true);
+}
+
+/// Restores max-heap property for subtree of `i` in heap `v[lo..hi]`, where children of `i` are
+/// `2i + 1` and `2i + 2` (indices are relative to `lo`)
+fn __sift_down_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    lo: usize,
+    hi: usize,
+    mut i: usize,
+) {
+    let n: usize = hi - lo;
+    loop {
+        let (l, r): (usize, usize) = (2 * i + 1, 2 * i + 2);
+        let mut largest: usize = i;
+        if l < n && s.greater(lo + l, lo + largest) {
+            largest = l;
+        }
+        if r < n && s.greater(lo + r, lo + largest) {
+            largest = r;
+        }
+        if largest == i {
+            return;
+        }
+        s.swap(lo + i, lo + largest);
+        i = largest;
+    }
+}
+
+/// Restores max-heap property for subtree of `i` in `v`, where children of `i` are `2i + 1` and
+/// `2i + 2`
+fn __sift_down<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], i: usize, cmp: &mut F) {
+    let n: usize = v.len();
+    let observer = |_: SortEvent| {};
+    __sift_down_range(&mut ObservedSlice { v, cmp, observer }, 0, n, i);
+}
+
+/// Sorts `v[lo..hi]` with heapsort in O(NlogN), also used by introsort after its depth limit
+fn __heap_sort_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    lo: usize,
+    hi: usize,
+) {
+    let n: usize = hi - lo;
+    for i in (0..n / 2).rev() {
+        __sift_down_range(s, lo, hi, i);
+    }
+    // Move maximum to the end of unsorted part
+    for end in (1..n).rev() {
+        s.swap(lo, lo + end);
+        __sift_down_range(s, lo, lo + end, 0);
+    }
+}
+
+observed_sort!(
+    /// Sorts slice using [heapsort algorithm](https://en.wikipedia.org/wiki/Heapsort): builds a
+    /// max-heap in place and moves its maximum to the end of unsorted part. Time complexity is
+    /// O(NlogN) in the worst case, additional memory is O(1). Sort is not stable.
+    heap_sort, heap_sort_by, heap_sort_by_key, heap_sort_with_observer, __observed_heap_sort
+);
+
+/// Heapsort of the whole slice
+fn __observed_heap_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
+    __heap_sort_range(s, 0, n);
+}
+
+/// Sorts `v` with heapsort in O(NlogN)
+fn __heap_sort<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], cmp: &mut F) {
+    let observer = |_: SortEvent| {};
+    __observed_heap_sort(&mut ObservedSlice { v, cmp, observer });
+}
+
+/// Sorts `v[lo..hi]` with introsort: quicksort with ninther pivot and three-way partitioning,
+/// until depth limit is reached
 ///
-///     let mut min_element = v[0];
-///     let mut max_element = v[0];
+/// # Arguments:
+/// * s - observed slice
+/// * lo - left bound of sorted part
+/// * hi - right bound of sorted part (non-inclusive)
+/// * depth_limit - number of partitioning levels left before switching to heapsort
+fn __intro_sort_range<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+    mut lo: usize,
+    mut hi: usize,
+    mut depth_limit: usize,
+) {
+    while hi - lo > INSERTION_SORT_THRESHOLD {
+        if depth_limit == 0 {
+            __heap_sort_range(s, lo, hi);
+            return;
+        }
+        depth_limit -= 1;
+
+        let pivot: usize = __choose_pivot_range(s, lo, hi, PivotStrategy::Ninther);
+        let (l, r): (usize, usize) = __partition_three_way_range(s, lo, hi, pivot);
+
+        if l - lo < hi - r {
+            __intro_sort_range(s, lo, l, depth_limit);
+            lo = r;
+        } else {
+            __intro_sort_range(s, r, hi, depth_limit);
+            hi = l;
+        }
+    }
+    __insertion_sort_range(s, lo, hi);
+}
+
+observed_sort!(
+    /// Sorts slice using [introsort algorithm](https://en.wikipedia.org/wiki/Introsort):
+    /// three-way quicksort with ninther pivot, which falls back to heapsort when recursion depth
+    /// exceeds `2 * log2(N)` and to insertion sort for partitions of 16 or fewer elements.
+    ///
+    /// Time complexity is O(NlogN) in the worst case, additional memory is O(logN). Sort is not
+    /// stable.
+    intro_sort, intro_sort_by, intro_sort_by_key, intro_sort_with_observer, __observed_intro_sort
+);
+
+/// Introsort of the whole slice, depth limit is `2 * log2(N)`
+fn __observed_intro_sort<T, F: FnMut(&T, &T) -> Ordering, O: FnMut(SortEvent)>(
+    s: &mut ObservedSlice<T, F, O>,
+) {
+    let n: usize = s.len();
+    if n == 0 {
+        return;
+    }
+    let depth_limit: usize = 2 * n.ilog2() as usize;
+    __intro_sort_range(s, 0, n, depth_limit);
+}
+
+/// Integer key for counting sort and radix sort. Key is mapped to an unsigned number with the
+/// same order, so signed keys are sorted correctly.
+pub trait IntegerKey: Copy + Ord {
+    /// Number of bits in a key
+    const BITS: u32;
+
+    /// Returns unsigned number with the same order as keys: `a < b` if and only if
+    /// `a.to_ordered_bits() < b.to_ordered_bits()`. Only lower `BITS` bits may be set
+    fn to_ordered_bits(self) -> u128;
+}
+
+/// Implements `IntegerKey` for unsigned and signed integer types. Sign bit of signed integers is
+/// flipped, so negative numbers go before non-negative ones
+macro_rules! impl_integer_key {
+    (unsigned: $($u:ty),*; signed: $($i:ty => $iu:ty),*) => {
+        $(
+            impl IntegerKey for $u {
+                const BITS: u32 = <$u>::BITS;
+
+                fn to_ordered_bits(self) -> u128 {
+                    return self as u128;
+                }
+            }
+        )*
+        $(
+            impl IntegerKey for $i {
+                const BITS: u32 = <$i>::BITS;
+
+                fn to_ordered_bits(self) -> u128 {
+                    return (self as $iu ^ (1 << (<$i>::BITS - 1))) as u128;
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_key!(
+    unsigned: u8, u16, u32, u64, u128, usize;
+    signed: i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+/// Rearranges `v`, so `i`-th element becomes initial `v[order[i]]`
 ///
-///     for &el in v.iter() {
-///         if el < min_element { min_element = el; }
-///         if max_element < el { max_element = el; }
+/// # Arguments:
+/// * `v` - slice, that will be rearranged
+/// * `order` - permutation of indices of `v`
+fn __apply_order<T: Clone>(v: &mut [T], order: &[usize]) {
+    let sorted: Vec<T> = order.iter().map(|&i| v[i].clone()).collect();
+    v.clone_from_slice(&sorted);
+}
+
+/// Maximal length of range of keys, for which counting sort allocates an array of counters. For
+/// wider ranges counting sort falls back to radix sort
+fn __max_counting_range(n: usize) -> u128 {
+    return 4 * n as u128 + 1024;
+}
+
+/// Sorts slice of integers using
+/// [counting sort algorithm](https://en.wikipedia.org/wiki/Counting_sort), time complexity is
+/// O(N + K), additional memory is O(N + K), where `K` is a length of `[min_element, max_element]`.
 ///
-///         let new_cnt: u64;
-///         match counter.get(&el) {
-///             Some(old_cnt) => new_cnt = old_cnt + 1,
-///             None => new_cnt = 1
-///         }
+/// If `K` is greater than `4 * N + 1024`, slice is sorted with `radix_sort` instead, so sparse
+/// values from a wide range don't allocate huge array of counters.
 ///
-///         counter.insert(el, new_cnt);
-///     }
+/// # Arguments:
+/// * v - mutable slice of integers, that will be sorted
+pub fn counting_sort<T: IntegerKey>(v: &mut [T]) {
+    counting_sort_by_key(v, |&x| x);
+}
+
+/// Sorts slice using [counting sort algorithm](https://en.wikipedia.org/wiki/Counting_sort) by
+/// integer keys, extracted from elements. Time complexity is O(N + K), additional memory is
+/// O(N + K), where `K` is a length of `[min_key, max_key]`. Every key is extracted once.
 ///
-///     let mut v_idx = 0;
-///     for val in min_element..=max_element {
-///         if !counter.contains_key(&val) { continue; }
-///         for _ in 0..counter[&val] {
-///             v[v_idx] = val;
-///             v_idx += 1;
-///         }
-///     }
-/// } ```
-/// </details>
+/// If `K` is greater than `4 * N + 1024`, slice is sorted with `radix_sort_by_key` instead.
 ///
+/// Sort is stable: elements with equal keys keep their relative order.
 ///
 /// # Arguments:
-/// * v - mutable vector of `i32`s, that will be sorted
-pub fn counting_sort(v: &mut Vec<i32>) {
-    let n = v.len();
+/// * v - mutable slice, that will be sorted
+/// * f - key extraction function
+pub fn counting_sort_by_key<T: Clone, K: IntegerKey, F: FnMut(&T) -> K>(v: &mut [T], mut f: F) {
+    let n: usize = v.len();
     if n == 0 {
         return;
     }
 
-    use std::collections::HashMap;
-    // Count occurrences of elements
-    let mut counter = HashMap::new();
+    let keys: Vec<u128> = v.iter().map(|x| f(x).to_ordered_bits()).collect();
+    let min_key: u128 = *keys.iter().min().unwrap();
+    let max_key: u128 = *keys.iter().max().unwrap();
+    // Range of sparse keys is too wide for array of counters
+    if max_key - min_key >= __max_counting_range(n) {
+        __radix_sort_keys(v, &keys, K::BITS);
+        return;
+    }
+    let range: usize = (max_key - min_key) as usize + 1;
+
+    // `starts[k]` is number of keys less than `min_key + k`, so it is position of the first
+    // element with key `min_key + k` in sorted slice
+    let mut starts: Vec<usize> = vec![0; range + 1];
+    for &key in &keys {
+        starts[(key - min_key) as usize + 1] += 1;
+    }
+    for k in 1..=range {
+        starts[k] += starts[k - 1];
+    }
 
-    // Minimum and maximum elements for range
-    let mut min_element = v[0];
-    let mut max_element = v[0];
+    let mut order: Vec<usize> = vec![0; n];
+    for (i, &key) in keys.iter().enumerate() {
+        let k: usize = (key - min_key) as usize;
+        order[starts[k]] = i;
+        starts[k] += 1;
+    }
+    __apply_order(v, &order);
+}
 
-    for &el in v.iter() {
-        // Update minimum and maximum
-        if el < min_element {
-            min_element = el;
+/// Sorts slice of integers using
+/// [LSD radix sort algorithm](https://en.wikipedia.org/wiki/Radix_sort#Least_significant_digit),
+/// time complexity is O(N * B), additional memory is O(N), where `B` is number of bytes in a
+/// type. Unlike `counting_sort` it doesn't depend on range of values.
+///
+/// # Arguments:
+/// * v - mutable slice of integers, that will be sorted
+pub fn radix_sort<T: IntegerKey>(v: &mut [T]) {
+    radix_sort_by_key(v, |&x| x);
+}
+
+/// Sorts slice using
+/// [LSD radix sort algorithm](https://en.wikipedia.org/wiki/Radix_sort#Least_significant_digit)
+/// by integer keys, extracted from elements. Keys are sorted byte by byte, from the least
+/// significant one, with stable counting sort. Time complexity is O(N * B), where `B` is number of
+/// bytes in a key type. Every key is extracted once.
+///
+/// Sort is stable: elements with equal keys keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * f - key extraction function
+pub fn radix_sort_by_key<T: Clone, K: IntegerKey, F: FnMut(&T) -> K>(v: &mut [T], mut f: F) {
+    let keys: Vec<u128> = v.iter().map(|x| f(x).to_ordered_bits()).collect();
+    __radix_sort_keys(v, &keys, K::BITS);
+}
+
+/// Stably sorts `v` by already extracted keys with LSD radix sort
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * keys - `keys[i]` is ordered bits of key of `v[i]`
+/// * bits - number of bits in a key type
+fn __radix_sort_keys<T: Clone>(v: &mut [T], keys: &[u128], bits: u32) {
+    let n: usize = v.len();
+
+    // `order` is stable order of elements by already processed bytes of keys
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut next_order: Vec<usize> = vec![0; n];
+    for shift in (0..bits).step_by(8) {
+        let digit = |i: usize| ((keys[i] >> shift) & 0xFF) as usize;
+
+        let mut starts: [usize; 257] = [0; 257];
+        for i in 0..n {
+            starts[digit(i) + 1] += 1;
         }
-        if max_element < el {
-            max_element = el;
+        // Every key has the same byte, order doesn't change
+        if starts.contains(&n) {
+            continue;
+        }
+        for d in 1..=256 {
+            starts[d] += starts[d - 1];
         }
 
-        // Update occurrences' count
-        let new_cnt: u64;
-        match counter.get(&el) {
-            Some(old_cnt) => new_cnt = old_cnt + 1,
-            None => new_cnt = 1,
+        for &i in &order {
+            next_order[starts[digit(i)]] = i;
+            starts[digit(i)] += 1;
         }
+        std::mem::swap(&mut order, &mut next_order);
+    }
+    __apply_order(v, &order);
+}
 
-        counter.insert(el, new_cnt);
+/// Moves median of groups of 5 elements to the beginning of `v` and returns index of their
+/// median, which is not less than 30% and not greater than 70% of elements of `v`
+fn __median_of_medians<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], cmp: &mut F) -> usize {
+    let n: usize = v.len();
+    let groups: usize = n.div_ceil(5);
+    for g in 0..groups {
+        let (l, r): (usize, usize) = (5 * g, (5 * g + 5).min(n));
+        __insertion_sort(&mut v[l..r], cmp);
+        v.swap(g, l + (r - l) / 2);
     }
+    __select_linear(&mut v[..groups], groups / 2, cmp);
+    return groups / 2;
+}
 
-    // v_idx - index of updating element in v
-    let mut v_idx = 0;
-    // iterate over elements range
-    for val in min_element..=max_element {
-        // if val is not present in v
-        if !counter.contains_key(&val) {
-            continue;
+/// Rearranges `v`, so `v[k]` is `k`-th smallest element, using quickselect with median of
+/// medians pivot, time complexity is O(N) in the worst case
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * cmp - comparator, returns ordering of two elements
+fn __select_linear<T, F: FnMut(&T, &T) -> Ordering>(mut v: &mut [T], mut k: usize, cmp: &mut F) {
+    while v.len() > 5 {
+        let pivot: usize = __median_of_medians(v, cmp);
+        let (lt, gt): (usize, usize) = __partition_three_way(v, pivot, cmp);
+        if k < lt {
+            v = &mut std::mem::take(&mut v)[..lt];
+        } else if k >= gt {
+            v = &mut std::mem::take(&mut v)[gt..];
+            k -= gt;
+        } else {
+            return;
+        }
+    }
+    __insertion_sort(v, cmp);
+}
+
+/// Rearranges `v`, so `v[k]` is `k`-th smallest element, using quickselect with three-way
+/// partitioning
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * strategy - pivot strategy
+/// * depth_limit - number of partitioning levels before switching to median of medians, `None`
+///   for no limit
+/// * cmp - comparator, returns ordering of two elements
+fn __quick_select<T, F: FnMut(&T, &T) -> Ordering>(
+    mut v: &mut [T],
+    mut k: usize,
+    strategy: PivotStrategy,
+    mut depth_limit: Option<usize>,
+    cmp: &mut F,
+) {
+    while v.len() > INSERTION_SORT_THRESHOLD {
+        match depth_limit {
+            Some(0) => {
+                __select_linear(v, k, cmp);
+                return;
+            }
+            Some(depth) => depth_limit = Some(depth - 1),
+            None => {}
         }
 
-        // change v
-        for _ in 0..counter[&val] {
-            v[v_idx] = val;
-            v_idx += 1;
+        let pivot: usize = __choose_pivot(v, strategy, cmp);
+        let (lt, gt): (usize, usize) = __partition_three_way(v, pivot, cmp);
+        if k < lt {
+            v = &mut std::mem::take(&mut v)[..lt];
+        } else if k >= gt {
+            v = &mut std::mem::take(&mut v)[gt..];
+            k -= gt;
+        } else {
+            return;
         }
     }
+    __insertion_sort(v, cmp);
+}
+
+/// Rearranges slice using [quickselect algorithm](https://en.wikipedia.org/wiki/Quickselect), so
+/// `v[k]` is the element, that would be there if `v` was sorted, `v[..k]` are not greater and
+/// `v[k + 1..]` are not less than it. Time complexity is O(N) on average, but O(N^2) in the
+/// worst case (depends on `pivot` strategy).
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * pivot - pivot strategy, such as `PivotStrategy::Random`
+pub fn quick_select<T: Ord>(v: &mut [T], k: usize, pivot: PivotStrategy) {
+    quick_select_by(v, k, pivot, T::cmp);
+}
+
+/// Rearranges slice using [quickselect algorithm](https://en.wikipedia.org/wiki/Quickselect) with
+/// comparator, so `v[k]` is the element, that would be there if `v` was sorted
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * pivot - pivot strategy, such as `PivotStrategy::Random`
+/// * cmp - comparator, returns ordering of two elements
+pub fn quick_select_by<T, F: FnMut(&T, &T) -> Ordering>(
+    v: &mut [T],
+    k: usize,
+    pivot: PivotStrategy,
+    mut cmp: F,
+) {
+    assert!(k < v.len());
+    __quick_select(v, k, pivot, None, &mut cmp);
+}
+
+/// Rearranges slice using [quickselect algorithm](https://en.wikipedia.org/wiki/Quickselect) by
+/// keys, extracted from elements, so `v[k]` is the element, that would be there if `v` was sorted
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * pivot - pivot strategy, such as `PivotStrategy::Random`
+/// * f - key extraction function
+pub fn quick_select_by_key<T, K: Ord, F: FnMut(&T) -> K>(
+    v: &mut [T],
+    k: usize,
+    pivot: PivotStrategy,
+    mut f: F,
+) {
+    quick_select_by(v, k, pivot, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Rearranges slice using introselect: quickselect with ninther pivot, which falls back to
+/// [median of medians](https://en.wikipedia.org/wiki/Median_of_medians) when recursion depth
+/// exceeds `2 * log2(N)`. After it `v[k]` is the element, that would be there if `v` was sorted,
+/// `v[..k]` are not greater and `v[k + 1..]` are not less than it.
+///
+/// Time complexity is O(N) in the worst case. For example, median of `v` is
+/// `v[v.len() / 2]` after `nth_element(v, v.len() / 2)`.
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+pub fn nth_element<T: Ord>(v: &mut [T], k: usize) {
+    nth_element_by(v, k, T::cmp);
+}
+
+/// Rearranges slice using introselect with comparator, so `v[k]` is the element, that would be
+/// there if `v` was sorted. Time complexity is O(N) in the worst case.
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * cmp - comparator, returns ordering of two elements
+pub fn nth_element_by<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], k: usize, mut cmp: F) {
+    assert!(k < v.len());
+    let depth_limit: usize = 2 * v.len().ilog2() as usize;
+    __quick_select(v, k, PivotStrategy::Ninther, Some(depth_limit), &mut cmp);
+}
+
+/// Rearranges slice using introselect by keys, extracted from elements, so `v[k]` is the element,
+/// that would be there if `v` was sorted. Time complexity is O(N) in the worst case.
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * f - key extraction function
+pub fn nth_element_by_key<T, K: Ord, F: FnMut(&T) -> K>(v: &mut [T], k: usize, mut f: F) {
+    nth_element_by(v, k, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Rearranges slice using [median of medians algorithm](https://en.wikipedia.org/wiki/Median_of_medians),
+/// so `v[k]` is the element, that would be there if `v` was sorted. Time complexity is O(N) in
+/// the worst case, but with a larger constant than `nth_element`.
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+pub fn median_of_medians_select<T: Ord>(v: &mut [T], k: usize) {
+    median_of_medians_select_by(v, k, T::cmp);
+}
+
+/// Rearranges slice using [median of medians algorithm](https://en.wikipedia.org/wiki/Median_of_medians)
+/// with comparator, so `v[k]` is the element, that would be there if `v` was sorted
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - index of element in sorted order
+/// * cmp - comparator, returns ordering of two elements
+pub fn median_of_medians_select_by<T, F: FnMut(&T, &T) -> Ordering>(
+    v: &mut [T],
+    k: usize,
+    mut cmp: F,
+) {
+    assert!(k < v.len());
+    __select_linear(v, k, &mut cmp);
+}
+
+/// Sorts `k` smallest elements of slice and places them into `v[..k]`, order of `v[k..]` is
+/// unspecified. Time complexity is O(N + KlogK).
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - number of smallest elements to sort, whole slice is sorted if it is not less than length
+pub fn partial_sort<T: Ord>(v: &mut [T], k: usize) {
+    partial_sort_by(v, k, T::cmp);
+}
+
+/// Sorts `k` smallest elements of slice according to comparator and places them into `v[..k]`,
+/// order of `v[k..]` is unspecified. Time complexity is O(N + KlogK).
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - number of smallest elements to sort, whole slice is sorted if it is not less than length
+/// * cmp - comparator, returns ordering of two elements
+pub fn partial_sort_by<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], k: usize, mut cmp: F) {
+    if k == 0 {
+        return;
+    }
+    if k < v.len() {
+        nth_element_by(v, k - 1, &mut cmp);
+    }
+    let k: usize = k.min(v.len());
+    intro_sort_by(&mut v[..k], cmp);
+}
+
+/// Sorts `k` elements of slice with the smallest keys and places them into `v[..k]`, order of
+/// `v[k..]` is unspecified. Time complexity is O(N + KlogK).
+///
+/// # Arguments:
+/// * v - mutable slice
+/// * k - number of smallest elements to sort, whole slice is sorted if it is not less than length
+/// * f - key extraction function
+pub fn partial_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(v: &mut [T], k: usize, mut f: F) {
+    partial_sort_by(v, k, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Moves the last element of max-heap `v` up, while it is greater than its parent
+fn __sift_up<T, F: FnMut(&T, &T) -> Ordering>(v: &mut [T], cmp: &mut F) {
+    let mut i: usize = v.len() - 1;
+    while i > 0 {
+        let parent: usize = (i - 1) / 2;
+        if cmp(&v[i], &v[parent]) != Ordering::Greater {
+            return;
+        }
+        v.swap(i, parent);
+        i = parent;
+    }
+}
+
+/// Returns `k` smallest elements of iterator in sorted order. Elements are consumed one by one and
+/// only `k` of them are stored (in a max-heap), so it can be used on streams. Time complexity is
+/// O(NlogK), additional memory is O(K).
+///
+/// # Arguments:
+/// * iter - iterator or collection of elements
+/// * k - number of smallest elements to return
+pub fn top_k<T: Ord, I: IntoIterator<Item = T>>(iter: I, k: usize) -> Vec<T> {
+    return top_k_by(iter, k, T::cmp);
+}
+
+/// Returns `k` smallest elements of iterator according to comparator in sorted order, storing
+/// only `k` of them. Time complexity is O(NlogK), additional memory is O(K).
+///
+/// # Arguments:
+/// * iter - iterator or collection of elements
+/// * k - number of smallest elements to return
+/// * cmp - comparator, returns ordering of two elements
+pub fn top_k_by<T, I: IntoIterator<Item = T>, F: FnMut(&T, &T) -> Ordering>(
+    iter: I,
+    k: usize,
+    mut cmp: F,
+) -> Vec<T> {
+    // `heap` is a max-heap of the smallest elements seen so far
+    let mut heap: Vec<T> = Vec::with_capacity(k);
+    if k == 0 {
+        return heap;
+    }
+    for el in iter {
+        if heap.len() < k {
+            heap.push(el);
+            __sift_up(&mut heap, &mut cmp);
+        } else if cmp(&el, &heap[0]) == Ordering::Less {
+            // Replace the largest stored element
+            heap[0] = el;
+            __sift_down(&mut heap, 0, &mut cmp);
+        }
+    }
+    __heap_sort(&mut heap, &mut cmp);
+    return heap;
+}
+
+/// Returns `k` elements of iterator with the smallest keys in sorted order, storing only `k` of
+/// them. Time complexity is O(NlogK), additional memory is O(K).
+///
+/// # Arguments:
+/// * iter - iterator or collection of elements
+/// * k - number of smallest elements to return
+/// * f - key extraction function
+pub fn top_k_by_key<T, I: IntoIterator<Item = T>, K: Ord, F: FnMut(&T) -> K>(
+    iter: I,
+    k: usize,
+    mut f: F,
+) -> Vec<T> {
+    return top_k_by(iter, k, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Configuration of parallel sorts
+///
+/// # Fields:
+/// * `threads` - maximum number of threads, that sort at the same time
+/// * `sequential_cutoff` - slices of this length or shorter are sorted by a sequential sort
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParallelConfig {
+    pub threads: usize,
+    pub sequential_cutoff: usize,
+}
+
+impl Default for ParallelConfig {
+    /// Returns configuration with number of threads equal to available parallelism and cutoff of
+    /// 8192 elements
+    fn default() -> Self {
+        return ParallelConfig {
+            threads: std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            sequential_cutoff: 1 << 13,
+        };
+    }
+}
+
+/// Sorts `v` with merge sort, where halves are sorted in parallel by `threads` threads
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * threads - number of threads, that may be used
+/// * sequential_cutoff - slices of this length or shorter are sorted by `merge_sort_by`
+/// * cmp - comparator, returns ordering of two elements
+fn __parallel_merge_sort<T: Clone + Send, F: Fn(&T, &T) -> Ordering + Sync>(
+    v: &mut [T],
+    threads: usize,
+    sequential_cutoff: usize,
+    cmp: &F,
+) {
+    let n: usize = v.len();
+    if threads <= 1 || n <= sequential_cutoff.max(1) {
+        merge_sort_by(v, cmp);
+        return;
+    }
+
+    let mid: usize = n / 2;
+    let left_threads: usize = threads / 2;
+    let (left, right) = v.split_at_mut(mid);
+    std::thread::scope(|scope| {
+        scope.spawn(|| __parallel_merge_sort(left, left_threads, sequential_cutoff, cmp));
+        __parallel_merge_sort(right, threads - left_threads, sequential_cutoff, cmp);
+    });
+
+    let mut buffer: Vec<T> = Vec::with_capacity(mid);
+    __merge(v, mid, &mut buffer, &mut &cmp);
+}
+
+/// Sorts slice using [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort) with
+/// [scoped threads](https://doc.rust-lang.org/std/thread/fn.scope.html): halves are sorted in
+/// parallel until there are no free threads or slices are short, then `merge_sort` is used.
+/// Time complexity is O(NlogN / P + N), where `P` is number of threads, additional memory is O(N).
+///
+/// Sort is stable: equal elements keep their relative order.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * config - number of threads and sequential cutoff, such as `ParallelConfig::default()`
+pub fn parallel_merge_sort<T: Ord + Clone + Send>(v: &mut [T], config: ParallelConfig) {
+    parallel_merge_sort_by(v, config, T::cmp);
+}
+
+/// Sorts slice using parallel [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort)
+/// with comparator, which is shared between threads. Sort is stable.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * config - number of threads and sequential cutoff, such as `ParallelConfig::default()`
+/// * cmp - comparator, returns ordering of two elements
+pub fn parallel_merge_sort_by<T: Clone + Send, F: Fn(&T, &T) -> Ordering + Sync>(
+    v: &mut [T],
+    config: ParallelConfig,
+    cmp: F,
+) {
+    __parallel_merge_sort(v, config.threads, config.sequential_cutoff, &cmp);
+}
+
+/// Sorts slice using parallel [merge sort algorithm](https://en.wikipedia.org/wiki/Merge_sort)
+/// by keys, extracted from elements. Sort is stable.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * config - number of threads and sequential cutoff, such as `ParallelConfig::default()`
+/// * f - key extraction function
+pub fn parallel_merge_sort_by_key<T: Clone + Send, K: Ord, F: Fn(&T) -> K + Sync>(
+    v: &mut [T],
+    config: ParallelConfig,
+    f: F,
+) {
+    parallel_merge_sort_by(v, config, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Number of sample elements per bucket in sample sort
+const SAMPLE_SORT_OVERSAMPLING: usize = 32;
+
+/// Sorts slice using [sample sort algorithm](https://en.wikipedia.org/wiki/Samplesort) with
+/// [scoped threads](https://doc.rust-lang.org/std/thread/fn.scope.html):
+/// 1. `P - 1` splitters are chosen from a sorted random sample, where `P` is number of threads
+/// 2. Elements are distributed into `P` buckets between splitters (bucket indices are computed in
+///    parallel)
+/// 3. Every bucket is sorted by its own thread with `intro_sort`
+///
+/// Slices shorter than sequential cutoff are sorted by `intro_sort`. Time complexity is
+/// O(NlogN / P + N) on average, additional memory is O(N). Sort is not stable.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * config - number of threads and sequential cutoff, such as `ParallelConfig::default()`
+pub fn parallel_sample_sort<T: Ord + Clone + Send + Sync>(v: &mut [T], config: ParallelConfig) {
+    parallel_sample_sort_by(v, config, T::cmp);
+}
+
+/// Sorts slice using parallel [sample sort algorithm](https://en.wikipedia.org/wiki/Samplesort)
+/// with comparator, which is shared between threads. Sort is not stable.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * config - number of threads and sequential cutoff, such as `ParallelConfig::default()`
+/// * cmp - comparator, returns ordering of two elements
+pub fn parallel_sample_sort_by<T: Clone + Send + Sync, F: Fn(&T, &T) -> Ordering + Sync>(
+    v: &mut [T],
+    config: ParallelConfig,
+    cmp: F,
+) {
+    let n: usize = v.len();
+    let buckets: usize = config.threads;
+    if buckets <= 1
+        || n <= config
+            .sequential_cutoff
+            .max(buckets * SAMPLE_SORT_OVERSAMPLING)
+    {
+        intro_sort_by(v, cmp);
+        return;
+    }
+
+    // Splitters are evenly spaced elements of sorted sample
+    let mut rng = rand::thread_rng();
+    let mut sample: Vec<T> = (0..buckets * SAMPLE_SORT_OVERSAMPLING)
+        .map(|_| v[rng.gen_range(0..n)].clone())
+        .collect();
+    intro_sort_by(&mut sample, &cmp);
+    let splitters: Vec<T> = (1..buckets)
+        .map(|i| sample[i * SAMPLE_SORT_OVERSAMPLING].clone())
+        .collect();
+
+    // `bucket_of[i]` is number of splitters, that are not greater than `v[i]`
+    let mut bucket_of: Vec<usize> = vec![0; n];
+    let chunk: usize = n.div_ceil(buckets);
+    std::thread::scope(|scope| {
+        for (values, bucket_of) in v.chunks(chunk).zip(bucket_of.chunks_mut(chunk)) {
+            let (splitters, cmp) = (&splitters, &cmp);
+            scope.spawn(move || {
+                for (x, bucket) in values.iter().zip(bucket_of.iter_mut()) {
+                    *bucket = splitters.partition_point(|s| cmp(s, x) != Ordering::Greater);
+                }
+            });
+        }
+    });
+
+    // Stable distribution of elements into buckets, like in counting sort
+    let mut starts: Vec<usize> = vec![0; buckets + 1];
+    for &bucket in &bucket_of {
+        starts[bucket + 1] += 1;
+    }
+    for bucket in 1..=buckets {
+        starts[bucket] += starts[bucket - 1];
+    }
+    let mut order: Vec<usize> = vec![0; n];
+    let mut next: Vec<usize> = starts.clone();
+    for (i, &bucket) in bucket_of.iter().enumerate() {
+        order[next[bucket]] = i;
+        next[bucket] += 1;
+    }
+    __apply_order(v, &order);
+
+    // Every bucket is sorted by its own thread
+    std::thread::scope(|scope| {
+        let mut rest: &mut [T] = v;
+        for bucket in 0..buckets {
+            let (bucket_values, tail) = rest.split_at_mut(starts[bucket + 1] - starts[bucket]);
+            rest = tail;
+            let cmp: &F = &cmp;
+            scope.spawn(move || intro_sort_by(bucket_values, cmp));
+        }
+    });
+}
+
+/// Sorts slice using parallel [sample sort algorithm](https://en.wikipedia.org/wiki/Samplesort)
+/// by keys, extracted from elements. Sort is not stable.
+///
+/// # Arguments:
+/// * v - mutable slice, that will be sorted
+/// * config - number of threads and sequential cutoff, such as `ParallelConfig::default()`
+/// * f - key extraction function
+pub fn parallel_sample_sort_by_key<T: Clone + Send + Sync, K: Ord, F: Fn(&T) -> K + Sync>(
+    v: &mut [T],
+    config: ParallelConfig,
+    f: F,
+) {
+    parallel_sample_sort_by(v, config, |a, b| f(a).cmp(&f(b)));
 }
@@ -0,0 +1,159 @@
+use crate::monoid::{Idempotent, Monoid};
+
+/// [Sparse table](https://en.wikipedia.org/wiki/Range_minimum_query#Solution_using_constant_time_and_linearithmic_space),
+/// that allows to compute idempotent associative function (such as minimum, maximum or gcd) on a
+/// segment of a static array in O(1).
+///
+/// `table[k][i]` contains result for `v[i..i + 2^k]`, so any segment is covered by two
+/// (possibly overlapping) segments of length `2^k`.
+///
+/// Described [here](https://cp-algorithms.com/data_structures/sparse-table.html).
+///
+/// Memory complexity - O(NlogN)
+///
+/// Time complexity:
+/// * `new` - O(NlogN)
+/// * `get` - O(1)
+pub struct SparseTable<T: Clone, M: Idempotent<T>> {
+    /// `table[k]` contains results for all segments of length `2^k`
+    table: Vec<Vec<T>>,
+    /// `n` is initial vector's size
+    n: usize,
+    /// `monoid` provides an idempotent associative combination function, such as minimum
+    monoid: M,
+}
+
+/// Sparse table generic implementation.
+///
+/// Type `T` must support `Clone` for moving elements from table.
+impl<T: Clone, M: Idempotent<T>> SparseTable<T, M> {
+    /// Constructs and returns sparse table based on given vector and idempotent monoid
+    ///
+    /// # Arguments:
+    /// * `v` - An initial vector
+    /// * `monoid` - An idempotent monoid, which will be computed on query, such as `Min` or `Gcd`
+    pub fn new(v: &[T], monoid: M) -> SparseTable<T, M> {
+        let n: usize = v.len();
+        let mut table: Vec<Vec<T>> = vec![v.to_vec()];
+
+        // Segment of length `2^k` is combined from two halves of length `2^(k - 1)`
+        let mut len: usize = 2;
+        while len <= n {
+            let prev: &Vec<T> = &table[table.len() - 1];
+            let level: Vec<T> = (0..=n - len)
+                .map(|i| monoid.combine(&prev[i], &prev[i + len / 2]))
+                .collect();
+            table.push(level);
+            len *= 2;
+        }
+
+        return SparseTable { table, n, monoid };
+    }
+
+    /// Returns `combine` result for `v[query_l..query_r]` query
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, query_l: usize, query_r: usize) -> T {
+        // Assert query bounds
+        assert!(query_l < query_r && query_r <= self.n);
+
+        // Two segments of length `2^k` starting at `query_l` and ending at `query_r`
+        let k: usize = (query_r - query_l).ilog2() as usize;
+        return self
+            .monoid
+            .combine(&self.table[k][query_l], &self.table[k][query_r - (1 << k)]);
+    }
+}
+
+/// Disjoint sparse table, that allows to compute any associative function (such as sum or matrix
+/// product) on a segment of a static array in O(1).
+///
+/// Array is padded with identities to a power of two. On level `h` the array is split into blocks
+/// of length `2^(h + 1)`, and for every block suffix results of its left half and prefix results
+/// of its right half are stored. Any segment of length at least 2 crosses the middle of exactly
+/// one block on level `h`, where `h` is the highest differing bit of its ends, so its result is
+/// combined from one suffix and one prefix without overlapping.
+///
+/// Described [here](https://discuss.codechef.com/t/tutorial-disjoint-sparse-table/17404).
+///
+/// Memory complexity - O(NlogN)
+///
+/// Time complexity:
+/// * `new` - O(NlogN)
+/// * `get` - O(1)
+pub struct DisjointSparseTable<T: Clone, M: Monoid<T>> {
+    /// `table[h][i]` contains suffix or prefix result of `i` in its block on level `h`
+    table: Vec<Vec<T>>,
+    /// `v` is initial vector padded with identities to a power of two
+    v: Vec<T>,
+    /// `n` is initial vector's size
+    n: usize,
+    /// `monoid` provides an identity element and an associative combination function, such as
+    /// addition or matrix multiplication
+    monoid: M,
+}
+
+/// Disjoint sparse table generic implementation.
+///
+/// Type `T` must support `Clone` for moving elements from table.
+impl<T: Clone, M: Monoid<T>> DisjointSparseTable<T, M> {
+    /// Constructs and returns disjoint sparse table based on given vector and monoid
+    ///
+    /// # Arguments:
+    /// * `v` - An initial vector
+    /// * `monoid` - A monoid, which will be computed on query, such as `Sum` or `Product`
+    pub fn new(v: &[T], monoid: M) -> DisjointSparseTable<T, M> {
+        let n: usize = v.len();
+        let size: usize = n.next_power_of_two();
+        let mut padded: Vec<T> = v.to_vec();
+        padded.resize(size, monoid.identity());
+
+        let levels: usize = size.trailing_zeros() as usize;
+        let mut table: Vec<Vec<T>> = Vec::with_capacity(levels);
+        for h in 0..levels {
+            let half: usize = 1 << h;
+            let mut level: Vec<T> = padded.clone();
+            for block_l in (0..size).step_by(2 * half) {
+                let mid: usize = block_l + half;
+                // Suffix results of the left half
+                for i in (block_l..mid - 1).rev() {
+                    level[i] = monoid.combine(&padded[i], &level[i + 1]);
+                }
+                // Prefix results of the right half
+                for i in mid + 1..block_l + 2 * half {
+                    level[i] = monoid.combine(&level[i - 1], &padded[i]);
+                }
+            }
+            table.push(level);
+        }
+
+        return DisjointSparseTable {
+            table,
+            v: padded,
+            n,
+            monoid,
+        };
+    }
+
+    /// Returns `combine` result for `v[query_l..query_r]` query
+    ///
+    /// # Arguments:
+    /// * `query_l` - left bound of query
+    /// * `query_r` - right bound of query (non-inclusive)
+    pub fn get(&self, query_l: usize, query_r: usize) -> T {
+        // Assert query bounds
+        assert!(query_l < query_r && query_r <= self.n);
+
+        let last: usize = query_r - 1;
+        if query_l == last {
+            return self.v[query_l].clone();
+        }
+        // Level, where `query_l` and `last` lie in different halves of the same block
+        let h: usize = (query_l ^ last).ilog2() as usize;
+        return self
+            .monoid
+            .combine(&self.table[h][query_l], &self.table[h][last]);
+    }
+}
@@ -0,0 +1,121 @@
+use rust_aads::algebra::{binary_exponentiation, extended_euclidean_gcd};
+use rust_aads::prelude::*;
+use rust_aads::sortings::{bubble_sort, counting_sort, insertion_sort, selection_sort};
+
+#[test]
+fn sortings_test() {
+    let sorted: Vec<i32> = vec![-3, 0, 1, 1, 2, 5, 8];
+    let sorts: [fn(&mut [i32]); 4] = [bubble_sort, selection_sort, insertion_sort, counting_sort];
+    for sort in sorts {
+        let mut v: Vec<i32> = vec![5, 1, -3, 8, 0, 2, 1];
+        sort(&mut v);
+        assert_eq!(v, sorted);
+    }
+}
+
+#[test]
+fn binary_exponentiation_test() {
+    assert_eq!(binary_exponentiation(3u64, 13), 1_594_323);
+    assert_eq!(binary_exponentiation(2.0f64, 10), 1024.0);
+}
+
+#[test]
+fn extended_euclidean_gcd_test() {
+    let (mut x, mut y) = (0, 0);
+    let gcd: i32 = extended_euclidean_gcd(240, 46, &mut x, &mut y);
+    assert_eq!(gcd, 2);
+    assert_eq!(240 * x + 46 * y, gcd);
+}
+
+#[test]
+fn matrix_test() {
+    let mut a: Matrix<i32> = Matrix::new(2, 2);
+    a.set(0, 0, 1);
+    a.set(1, 1, 2);
+    *a.access(0, 1) = 3;
+    assert_eq!(a.shape(), (2, 2));
+    assert_eq!(a.get(0, 1), Some(&3));
+    assert_eq!(a.get(2, 0), None);
+
+    let b: Matrix<i32> = (&a + &a).unwrap();
+    assert_eq!(b[0], vec![2, 6]);
+    assert_eq!(b[1], vec![0, 4]);
+
+    // [[1, 3], [0, 2]] has determinant 2, so its inverse is not an integer matrix
+    assert_eq!(a.determinant(), 2);
+    assert_eq!(a.rank(), 2);
+    assert!(a.inverse().is_none());
+    assert_eq!(a.solve(&[7, 4]), Some(vec![1, 2]));
+
+    let f: Matrix<f64> = Matrix::from_vec(vec![vec![1.0, 3.0], vec![0.0, 2.0]]).unwrap();
+    let inverse: Matrix<f64> = f.inverse().unwrap();
+    assert_eq!(inverse[0], vec![1.0, -1.5]);
+    assert_eq!(inverse[1], vec![0.0, 0.5]);
+
+    assert_eq!(a.transpose(), Matrix::from_fn(2, 2, |r, c| a[c][r]));
+    let i: Matrix<i32> = Matrix::identity(2);
+    assert_eq!(
+        a.concat_horizontal(&i).unwrap().to_string(),
+        "1 3 1 0\n0 2 0 1"
+    );
+
+    assert_eq!(
+        a.pow(3),
+        Some(Matrix::from_vec(vec![vec![1, 21], vec![0, 8]]).unwrap())
+    );
+    let column: Matrix<i32> = Matrix::from_vec(vec![vec![1], vec![1]]).unwrap();
+    let product: Matrix<i32> = (&a * &column).unwrap();
+    assert_eq!(product.iter().copied().collect::<Vec<i32>>(), vec![4, 2]);
+    assert_eq!(
+        &column * &a,
+        Err(ShapeMismatch {
+            expected: (1, 2),
+            got: (2, 2)
+        })
+    );
+    assert_eq!(a.strassen_mul(&i), Ok(a.clone()));
+
+    let c: Matrix<i32> = -(a * 3);
+    assert_eq!(c[0], vec![-3, -9]);
+}
+
+#[test]
+fn matrix_operators_test() {
+    // `a * b + c`, shape errors are propagated with `?`
+    let multiply_add = |a: &Matrix<i64>,
+                        b: &Matrix<i64>,
+                        c: &Matrix<i64>|
+     -> Result<Matrix<i64>, ShapeMismatch> { &(a * b)? + c };
+
+    let a: Matrix<i64> = Matrix::from_fn(2, 3, |r, c| (r + c) as i64);
+    let b: Matrix<i64> = a.transpose();
+    let c: Matrix<i64> = Matrix::identity(2);
+    let d: Matrix<i64> = multiply_add(&a, &b, &c).unwrap();
+    assert_eq!(d, Matrix::from_vec(vec![vec![6, 8], vec![8, 15]]).unwrap());
+
+    let error: ShapeMismatch = multiply_add(&a, &a, &c).unwrap_err();
+    assert_eq!(error.expected, (3, 3));
+    assert_eq!(error.got, (2, 3));
+    assert_eq!(
+        error.to_string(),
+        "matrix shape mismatch: expected 3x3, got 2x3"
+    );
+    assert!(multiply_add(&a, &b, &a).is_err());
+
+    let mut e: Matrix<i64> = d.clone();
+    e += &c;
+    e -= &d;
+    assert_eq!(e, c);
+    e *= 5;
+    e *= &a;
+    assert_eq!(e, &a * 5);
+    e += e.clone();
+    assert_eq!(e, a * 10);
+}
+
+#[test]
+#[should_panic(expected = "matrix shape mismatch")]
+fn matrix_assign_shape_mismatch_test() {
+    let mut a: Matrix<i32> = Matrix::new(2, 2);
+    a += Matrix::new(2, 3);
+}
@@ -0,0 +1,81 @@
+use rust_aads::prelude::*;
+
+#[test]
+fn fenwick_test() {
+    let mut fenwick = Fenwick::from_slice(&[1, 2, 3, 4, 5], Sum);
+    assert_eq!(fenwick.get(1, 4), 9);
+    fenwick.add(2, &-3);
+    assert_eq!(fenwick.prefix(5), 12);
+    assert_eq!(fenwick.lower_bound(&3), 1);
+
+    let mut range_fenwick = RangeFenwick::new(5, Sum);
+    range_fenwick.add(1, 4, &2i64);
+    assert_eq!(range_fenwick.get(0, 5), 6);
+    assert_eq!(range_fenwick.get(3, 5), 2);
+
+    let mut fenwick_2d = Fenwick2D::new(3, 3, Xor);
+    fenwick_2d.add(1, 1, &6u8);
+    fenwick_2d.add(2, 2, &3u8);
+    assert_eq!(fenwick_2d.get((0, 0), (3, 3)), 5);
+    assert_eq!(fenwick_2d.get((0, 0), (2, 2)), 6);
+}
+
+#[test]
+fn sparse_table_test() {
+    let v: Vec<i32> = vec![4, 8, 1, 7, 3];
+    let sparse_table = SparseTable::new(&v, Max);
+    assert_eq!(sparse_table.get(0, 5), 8);
+    assert_eq!(sparse_table.get(2, 5), 7);
+
+    let disjoint_sparse_table = DisjointSparseTable::new(&v, Sum);
+    assert_eq!(disjoint_sparse_table.get(0, 5), 23);
+    assert_eq!(disjoint_sparse_table.get(1, 4), 16);
+}
+
+#[test]
+fn dsu_test() {
+    let mut dsu = Dsu::new(5);
+    assert!(dsu.union(0, 1));
+    assert!(dsu.union(3, 4));
+    assert!(!dsu.union(1, 0));
+    assert!(dsu.same(0, 1));
+    assert!(!dsu.same(1, 3));
+    assert_eq!(dsu.size(4), 2);
+    assert_eq!(dsu.count(), 3);
+
+    let mut rollback_dsu = RollbackDsu::new(4);
+    rollback_dsu.union(0, 1);
+    let snapshot: usize = rollback_dsu.snapshot();
+    rollback_dsu.union(1, 2);
+    assert!(rollback_dsu.same(0, 2));
+    rollback_dsu.rollback(snapshot);
+    assert!(!rollback_dsu.same(0, 2));
+    assert!(rollback_dsu.same(0, 1));
+
+    let mut dsu_with_data = DsuWithData::new(&[5, 1, 7], Min);
+    dsu_with_data.union(0, 2);
+    assert_eq!(*dsu_with_data.data(2), 5);
+    dsu_with_data.union(2, 1);
+    assert_eq!(*dsu_with_data.data(0), 1);
+
+    let mut rollback_dsu_with_data = RollbackDsuWithData::new(&[5, 1, 7], Sum);
+    rollback_dsu_with_data.union(0, 2);
+    let snapshot: usize = rollback_dsu_with_data.snapshot();
+    rollback_dsu_with_data.union(2, 1);
+    assert_eq!(*rollback_dsu_with_data.data(0), 13);
+    rollback_dsu_with_data.rollback(snapshot);
+    assert_eq!(*rollback_dsu_with_data.data(0), 12);
+    assert_eq!(*rollback_dsu_with_data.data(1), 1);
+}
+
+#[test]
+fn indexed_heap_test() {
+    let mut heap: IndexedHeap<(u32, char)> = IndexedHeap::new();
+    let a: usize = heap.push((10, 'a'));
+    heap.push((5, 'b'));
+    heap.decrease_key(a, (1, 'a'));
+    assert_eq!(heap.peek(), Some(&(1, 'a')));
+    assert_eq!(heap.remove(a), (1, 'a'));
+    assert_eq!(heap.pop(), Some((5, 'b')));
+    assert!(heap.is_empty());
+}
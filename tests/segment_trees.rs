@@ -0,0 +1,97 @@
+use rust_aads::prelude::*;
+
+#[test]
+fn segment_tree_test() {
+    let mut seg_tree = SegmentTree::new(&[5, 1, 4, 2, 3], Min);
+    assert_eq!(seg_tree.get(0, 5), 1);
+    seg_tree.set(1, 10);
+    assert_eq!(seg_tree.get(0, 5), 2);
+    assert_eq!(seg_tree.get(0, 2), 5);
+
+    // First prefix from 0 with minimum less than 3 is [0, 4)
+    assert_eq!(seg_tree.max_right(0, |&x| x >= 3), 3);
+}
+
+#[test]
+fn segment_tree_fn_monoid_test() {
+    let seg_tree = SegmentTree::new(
+        &[String::from("a"), String::from("b"), String::from("c")],
+        FnMonoid::new(String::new(), |a: &String, b: &String| a.clone() + b),
+    );
+    assert_eq!(seg_tree.get(0, 3), "abc");
+    assert_eq!(seg_tree.get(1, 3), "bc");
+}
+
+#[test]
+fn lazy_segment_tree_test() {
+    let mut seg_tree = LazySegmentTree::new(
+        &[1i64, 2, 3, 4, 5],
+        Sum,
+        |value: &i64, add: &i64, len: usize| value + add * len as i64,
+        |old: &i64, new: &i64| old + new,
+    );
+    seg_tree.update(1, 4, 10);
+    assert_eq!(seg_tree.get(0, 5), 45);
+    assert_eq!(seg_tree.get(3, 5), 19);
+}
+
+#[test]
+fn bottom_up_segment_tree_test() {
+    let mut seg_tree = BottomUpSegmentTree::new(&[3u32, 6, 9, 12], Gcd);
+    assert_eq!(seg_tree.get(0, 4), 3);
+    seg_tree.set(0, 18);
+    assert_eq!(seg_tree.get(0, 4), 3);
+    assert_eq!(seg_tree.get(1, 3), 3);
+    assert_eq!(seg_tree.get(0, 2), 6);
+}
+
+#[test]
+fn persistent_segment_tree_test() {
+    let mut seg_tree = PersistentSegmentTree::new(&[1, 2, 3], Sum);
+    let version: usize = seg_tree.set(0, 1, 20);
+    assert_eq!(seg_tree.versions(), 2);
+    assert_eq!(seg_tree.get(0, 0, 3), 6);
+    assert_eq!(seg_tree.get(version, 0, 3), 24);
+
+    let kth = RangeKthSmallest::new(&[7, 1, 5, 3, 9]);
+    assert_eq!(*kth.kth_smallest(0, 5, 0), 1);
+    assert_eq!(*kth.kth_smallest(2, 5, 1), 5);
+}
+
+#[test]
+fn segment_tree_2d_test() {
+    let mut matrix: Matrix<i32> = Matrix::new(2, 3);
+    for r in 0..2 {
+        for c in 0..3 {
+            matrix.set(r, c, (r * 3 + c) as i32);
+        }
+    }
+
+    let mut seg_tree = SegmentTree2D::from_matrix(&matrix, Sum);
+    assert_eq!(seg_tree.shape(), (2, 3));
+    assert_eq!(seg_tree.get((0, 0), (2, 3)), 15);
+    seg_tree.set(1, 2, 0);
+    assert_eq!(seg_tree.get((1, 1), (2, 3)), 4);
+}
+
+#[test]
+fn dynamic_segment_tree_test() {
+    let mut seg_tree: DynamicSegmentTree<i64, i64, Max> =
+        DynamicSegmentTree::new(-1_000_000_000_000, 1_000_000_000_000, Max);
+    seg_tree.set(-5, 3);
+    seg_tree.set(999_999_999_999, 4);
+    assert_eq!(seg_tree.get(-1_000_000_000_000, 0), 3);
+    assert_eq!(seg_tree.get(-1_000_000_000_000, 1_000_000_000_000), 4);
+}
+
+#[test]
+fn lazy_dynamic_segment_tree_test() {
+    let mut seg_tree: LazyDynamicSegmentTree<i64, i64, Sum> =
+        LazyDynamicSegmentTree::new(-1_000_000_000_000, 1_000_000_000_000, Sum);
+    seg_tree.set(-5, 3);
+    seg_tree.set(999_999_999_999, 4);
+    assert_eq!(seg_tree.get(-1_000_000_000_000, 1_000_000_000_000), 7);
+
+    seg_tree.add(0, 10, 2);
+    assert_eq!(seg_tree.get(-10, 10), 23);
+}